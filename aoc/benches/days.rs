@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use util::bench::Measurement;
+use util::{InputSource, ParseError, Puzzle, Solution};
+
+struct Bench<'a> {
+    source: &'a InputSource,
+}
+
+impl aoc::Visitor for Bench<'_> {
+    type Output = Result<Vec<Measurement>, ParseError>;
+
+    fn visit<S: Solution>(self, puzzle: Puzzle, solution: S) -> Self::Output {
+        let dir = aoc::puzzle_dir(puzzle);
+        util::try_bench_source(&puzzle.to_string(), solution, self.source, &dir)
+    }
+}
+
+// `cargo bench -p aoc [-- DAYS...]`, e.g. `cargo bench -p aoc -- 15 19..=22` or `-- 2021 5`
+fn main() {
+    let mut selection = aoc::Selection::default();
+    for arg in std::env::args().skip(1).filter(|a| !a.starts_with('-')) {
+        selection.push(&arg).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    let source = InputSource::from_env();
+    let mut measurements = vec![];
+    for puzzle in selection.finish() {
+        if !source.label_in(&aoc::puzzle_dir(puzzle)).exists() {
+            eprintln!("{}: no input, skipping", puzzle);
+            continue;
+        }
+        match aoc::visit(puzzle, Bench { source: &source }) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    let json_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/bench.json");
+    util::bench::report(&measurements, Some(&json_path));
+}
@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use util::puzzle::{parse_day, parse_year, DEFAULT_YEAR};
+use util::{Puzzle, Solution};
+
+/// Something to do with a puzzle's solution, generic over its concrete type.
+pub trait Visitor {
+    type Output;
+
+    fn visit<S: Solution + Send + 'static>(self, puzzle: Puzzle, solution: S) -> Self::Output;
+}
+
+macro_rules! puzzles {
+    ($($year:literal => { $($day:literal => $krate:ident,)* },)*) => {
+        pub const PUZZLES: &[Puzzle] = &[$($(Puzzle::new($year, $day),)*)*];
+
+        pub fn visit<V: Visitor>(puzzle: Puzzle, visitor: V) -> V::Output {
+            match (puzzle.year, puzzle.day) {
+                $($(($year, $day) => visitor.visit(puzzle, $krate::solution()),)*)*
+                _ => panic!("{} is not registered", puzzle),
+            }
+        }
+    };
+}
+
+// Another year's crates go in `YEAR/dayDD`, under a block of their own.
+puzzles! {
+    2021 => {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        19 => day19,
+        20 => day20,
+        21 => day21,
+        22 => day22,
+        24 => day24,
+        25 => day25,
+    },
+}
+
+pub fn puzzle_dir(puzzle: Puzzle) -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    puzzle.dir_in(root)
+}
+
+/// Every solved puzzle from `year`.
+pub fn year(year: u16) -> Vec<Puzzle> {
+    PUZZLES.iter().copied().filter(|p| p.year == year).collect()
+}
+
+/// Parses `DAY`, `START..END`, `START..=END` or `all` within `year`, skipping unsolved days within
+/// ranges. Any of them can be prefixed with another year, as in `2021/day05` or `2021/1..=10`.
+pub fn parse_days(arg: &str, year: u16) -> Result<Vec<Puzzle>, String> {
+    let invalid = || format!("`{}` is not a day or range of days", arg);
+    let (year, spec) = match arg.split_once('/') {
+        Some((y, spec)) => (parse_year(y).ok_or_else(invalid)?, spec),
+        None => (year, arg),
+    };
+    if spec == "all" {
+        return Ok(self::year(year));
+    }
+
+    let parse = |s: &str| parse_day(s).ok_or_else(invalid);
+    let solved = |&day: &u8| PUZZLES.contains(&Puzzle::new(year, day));
+
+    let range = if let Some((start, end)) = spec.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = spec.split_once("..") {
+        parse(start)?..=parse(end)?.checked_sub(1).ok_or_else(invalid)?
+    } else {
+        let day = parse(spec)?;
+        if !solved(&day) {
+            return Err(format!("no solution for {}", Puzzle::new(year, day)));
+        }
+        day..=day
+    };
+
+    Ok(range
+        .filter(solved)
+        .map(|day| Puzzle::new(year, day))
+        .collect())
+}
+
+/// Whether `arg` is meant as puzzles, solved or not, rather than as an input's path: a year, `all`,
+/// or days and ranges of them, with or without a year in front.
+pub fn is_days(arg: &str) -> bool {
+    let spec = match arg.split_once('/') {
+        Some((year, spec)) if parse_year(year).is_some() => spec,
+        _ => arg,
+    };
+    let day = |part: &str| {
+        let digits = part.strip_prefix("day").unwrap_or(part);
+        digits.chars().all(|c| c.is_ascii_digit())
+    };
+    let mut parts = spec.split(['.', '=']).filter(|part| !part.is_empty());
+    spec == "all" || parts.clone().next().is_some() && parts.all(day)
+}
+
+/// Parses command-line puzzle specs. A bare year, as in `aoc 2021 5`, applies to the days after
+/// it, or means the whole year if no days follow. Days without a year are from `DEFAULT_YEAR`.
+pub struct Selection {
+    year: u16,
+    pending_year: bool,
+    asked: bool,
+    puzzles: Vec<Puzzle>,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            year: DEFAULT_YEAR,
+            pending_year: false,
+            asked: false,
+            puzzles: vec![],
+        }
+    }
+}
+
+impl Selection {
+    pub fn push(&mut self, arg: &str) -> Result<(), String> {
+        self.asked = true;
+        if arg.len() == 4 {
+            if let Some(year) = parse_year(arg) {
+                self.finish_year();
+                self.year = year;
+                self.pending_year = true;
+                return Ok(());
+            }
+        }
+        self.pending_year = false;
+        self.puzzles.extend(parse_days(arg, self.year)?);
+        Ok(())
+    }
+
+    fn finish_year(&mut self) {
+        if std::mem::take(&mut self.pending_year) {
+            self.puzzles.extend(year(self.year));
+        }
+    }
+
+    /// Everything from `DEFAULT_YEAR` if nothing was asked for.
+    pub fn finish(mut self) -> Vec<Puzzle> {
+        self.finish_year();
+        if !self.asked {
+            self.puzzles = year(DEFAULT_YEAR);
+        }
+        self.puzzles
+    }
+}
@@ -0,0 +1,415 @@
+use std::path::Path;
+use std::process::exit;
+use std::time::Duration;
+
+use aoc::{puzzle_dir, Selection, Visitor};
+use util::bench::{Measurement, Scaling};
+use util::isolate::{self, Outcome};
+use util::{Answers, Args, InputSource, ParseError, Puzzle, Solution};
+
+struct Run<'a> {
+    source: &'a InputSource,
+    dir: &'a Path,
+    parallel: bool,
+    timeout: Option<Duration>,
+}
+
+impl Visitor for Run<'_> {
+    type Output = isolate::Report;
+
+    fn visit<S: Solution + Send + 'static>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        util::solve_source_isolated(solution, self.source, self.dir, self.parallel, self.timeout)
+    }
+}
+
+struct Bench<'a> {
+    source: &'a InputSource,
+    dir: &'a Path,
+}
+
+impl Visitor for Bench<'_> {
+    type Output = Result<Vec<Measurement>, ParseError>;
+
+    fn visit<S: Solution>(self, puzzle: Puzzle, solution: S) -> Self::Output {
+        util::try_bench_source(&puzzle.to_string(), solution, self.source, self.dir)
+    }
+}
+
+struct Check<'a> {
+    source: &'a InputSource,
+    dir: &'a Path,
+}
+
+impl Visitor for Check<'_> {
+    type Output = Result<util::check::Report, ParseError>;
+
+    fn visit<S: Solution>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        util::check_source(&solution, self.source, self.dir)
+    }
+}
+
+struct CrossCheck<'a> {
+    source: &'a InputSource,
+    dir: &'a Path,
+}
+
+impl Visitor for CrossCheck<'_> {
+    type Output = Result<util::alt::CrossCheck, ParseError>;
+
+    fn visit<S: Solution>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        util::cross_check_source(&solution, self.source, self.dir)
+    }
+}
+
+struct Generate {
+    seed: u64,
+    size: usize,
+}
+
+impl Visitor for Generate {
+    type Output = Option<String>;
+
+    fn visit<S: Solution>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        solution.generate(self.seed, self.size)
+    }
+}
+
+struct Scale<'a> {
+    sizes: &'a [usize],
+    seed: u64,
+}
+
+impl Visitor for Scale<'_> {
+    type Output = Option<Result<Vec<Scaling>, ParseError>>;
+
+    fn visit<S: Solution>(self, puzzle: Puzzle, solution: S) -> Self::Output {
+        let config = util::bench::Config::from_env();
+        util::bench::bench_scaling(
+            &puzzle.to_string(),
+            &solution,
+            self.sizes,
+            self.seed,
+            &config,
+        )
+    }
+}
+
+/// A day's own binary, for the flags that only make sense for one day at a time.
+struct Single<'a> {
+    args: &'a Args,
+    dir: &'a Path,
+}
+
+impl Visitor for Single<'_> {
+    type Output = Result<(), ParseError>;
+
+    fn visit<S: Solution + Send + 'static>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        util::try_run_with(solution, self.args, self.dir)
+    }
+}
+
+struct Stream<'a> {
+    path: &'a Path,
+}
+
+impl Visitor for Stream<'_> {
+    type Output = Option<Result<Answers<String>, ParseError>>;
+
+    fn visit<S: Solution>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        let answers = solution.stream(self.path)?;
+        Some(answers.map(|answers| answers.to_strings()))
+    }
+}
+
+/// Solves each day a line at a time, for days that can take their input that way.
+fn stream(puzzles: &[Puzzle], args: &Args) {
+    let mut failures = vec![];
+    for &puzzle in puzzles {
+        let dir = puzzle_dir(puzzle);
+        let Some(path) = args.input.path_in(&dir) else {
+            usage("only files can be streamed, since each part reads the input again");
+        };
+        if !path.exists() {
+            println!("{}: no input at {}", puzzle, path.display());
+            continue;
+        }
+        println!("{}:", puzzle);
+        match aoc::visit(puzzle, Stream { path: &path }) {
+            None => println!("can't stream its input"),
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                failures.push(puzzle.to_string());
+            }
+            Some(Ok(answers)) => {
+                answers.print();
+                let answers = answers.strings();
+                if let Err(e) =
+                    util::check_ledger(&answers, &args.input, &dir, args.record, args.verify)
+                {
+                    eprintln!("{}", e);
+                    failures.push(puzzle.to_string());
+                }
+            }
+        }
+    }
+    if !failures.is_empty() {
+        eprintln!("\nFAILED to stream {}", failures.join(", "));
+        exit(1);
+    }
+}
+
+fn usage(msg: &str) -> ! {
+    eprintln!(
+        concat!(
+            "{}\nusage: aoc [PATH | - | --input NAME] [--jobs N] [--timeout SECS] {}\n",
+            "    [YEAR] [DAY | START..END | START..=END | all | YEAR/DAY]...",
+        ),
+        msg,
+        Args::FLAGS
+    );
+    exit(1);
+}
+
+fn main() {
+    // The days, and the flags that only the runner has, are taken before the rest are parsed the
+    // way a day's own binary parses them. Anything else is the input, within each day's directory.
+    let mut selection = Selection::default();
+    let mut jobs = None;
+    let mut timeout = Some(Duration::from_secs(60));
+    let args = Args::parse_with(std::env::args().skip(1), |arg, rest| {
+        if arg == "--jobs" {
+            let n = rest.next().and_then(|n| n.parse::<usize>().ok());
+            jobs = Some(n.filter(|&n| n > 0).ok_or("`--jobs` needs a number")?);
+        } else if arg == "--timeout" {
+            let secs = rest.next().and_then(|n| n.parse::<f64>().ok());
+            let secs = secs.filter(|secs| *secs >= 0.0);
+            let secs = secs.ok_or("`--timeout` needs a number of seconds")?;
+            timeout = (secs > 0.0).then(|| Duration::from_secs_f64(secs));
+        } else if aoc::is_days(arg) {
+            selection.push(arg)?;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    })
+    .unwrap_or_else(|e| usage(&e));
+    let puzzles = selection.finish();
+    if puzzles.is_empty() {
+        usage("no solved puzzles were selected");
+    }
+    let source = &args.input;
+    let one_day = |msg: &str| {
+        if puzzles.len() > 1 {
+            usage(msg);
+        }
+    };
+    if *source == InputSource::Stdin {
+        one_day("stdin can only be read for one day");
+    }
+
+    if let Some(size) = args.generate {
+        if *source == InputSource::Default {
+            one_day(
+                "`--generate` only prints one day's input, and `--input NAME` writes them instead",
+            );
+        }
+        let seed = args.seed;
+        for &puzzle in &puzzles {
+            let Some(input) = aoc::visit(puzzle, Generate { seed, size }) else {
+                println!("{}: no generator", puzzle);
+                continue;
+            };
+            if let Err(e) = util::write_generated(&input, source, &puzzle_dir(puzzle)) {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+        return;
+    }
+
+    if args.reduce.is_some() || args.visualize.is_some() {
+        one_day("`--reduce` and `--visualize` take one day at a time");
+        let (puzzle, dir) = (puzzles[0], puzzle_dir(puzzles[0]));
+        let args = &args;
+        if let Err(e) = aoc::visit(puzzle, Single { args, dir: &dir }) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+
+    if let Some(sizes) = &args.scale {
+        let mut scalings = vec![];
+        let seed = args.seed;
+        for &puzzle in &puzzles {
+            match aoc::visit(puzzle, Scale { sizes, seed }) {
+                None => println!("{}: no generator", puzzle),
+                Some(Ok(s)) => {
+                    print!("{}", util::bench::scaling_table(&s));
+                    scalings.extend(s);
+                }
+                Some(Err(e)) => eprintln!("{}", e),
+            }
+        }
+        if let Some(path) = &args.json {
+            std::fs::write(path, util::bench::scaling_json(&scalings))
+                .unwrap_or_else(|e| panic!("couldn't write {}: {}", path.display(), e));
+        }
+        return;
+    }
+
+    let no_input = |puzzle| {
+        let input_path = source.path_in(&puzzle_dir(puzzle))?;
+        (!input_path.exists()).then(|| format!("{}: no input at {}", puzzle, input_path.display()))
+    };
+
+    let (check, cross_check) = (args.check, args.cross_check);
+    if check || cross_check {
+        let mut failures = vec![];
+        for &puzzle in &puzzles {
+            if let Some(msg) = no_input(puzzle) {
+                println!("{}", msg);
+                continue;
+            }
+            let dir = puzzle_dir(puzzle);
+            let dir = &dir;
+            println!("{}:", puzzle);
+            let report = if check {
+                aoc::visit(puzzle, Check { source, dir }).map(|r| (r.to_string(), r.is_ok()))
+            } else {
+                aoc::visit(puzzle, CrossCheck { source, dir }).map(|r| (r.to_string(), r.is_ok()))
+            };
+            match report {
+                Ok((report, ok)) => {
+                    print!("{}", report);
+                    if !ok {
+                        failures.push(puzzle);
+                    }
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    failures.push(puzzle);
+                }
+            }
+        }
+        if !failures.is_empty() {
+            let puzzles = failures.iter().map(Puzzle::to_string).collect::<Vec<_>>();
+            let what = if check { "check" } else { "cross-check" };
+            eprintln!("\nFAILED to {} {}", what, puzzles.join(", "));
+            exit(1);
+        }
+        return;
+    }
+
+    if args.bench {
+        let mut measurements = vec![];
+        for &puzzle in &puzzles {
+            if let Some(msg) = no_input(puzzle) {
+                println!("{}", msg);
+                continue;
+            }
+            eprintln!("benchmarking {}...", puzzle);
+            let dir = puzzle_dir(puzzle);
+            match aoc::visit(puzzle, Bench { source, dir: &dir }) {
+                Ok(m) => measurements.extend(m),
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        util::bench::report(&measurements, args.json.as_deref());
+        return;
+    }
+
+    if args.stream {
+        stream(&puzzles, &args);
+        return;
+    }
+
+    // Days run on a pool of `jobs` threads, but are still reported in order. Allocations can only
+    // be told apart when there's one day at a time.
+    let jobs = match jobs {
+        _ if util::alloc::enabled() => 1,
+        Some(n) => n,
+        None => 1,
+    };
+    let (parallel, record, verify) = (args.parallel, args.record, args.verify);
+    let solve = |&puzzle: &Puzzle| {
+        if let Some(msg) = no_input(puzzle) {
+            return Err(msg);
+        }
+        let dir = puzzle_dir(puzzle);
+        let run = Run {
+            source,
+            dir: &dir,
+            parallel,
+            timeout,
+        };
+        Ok(aoc::visit(puzzle, run))
+    };
+
+    let mut timings = vec![];
+    let mut memory = vec![];
+    let mut failures = vec![];
+    let mut broken = vec![];
+    isolate::quiet_panics();
+    util::parallel::for_each_ordered(&puzzles, jobs, solve, |&puzzle, result| match result {
+        Err(msg) => println!("{}", msg),
+        Ok(Outcome::Done(Err(e))) => {
+            println!("{}:", puzzle);
+            eprintln!("{}", e);
+            broken.push(puzzle);
+        }
+        Ok(Outcome::Panicked(message)) => {
+            println!("{}:\nparsing panicked: {}", puzzle, message);
+            broken.push(puzzle);
+        }
+        Ok(Outcome::TimedOut(limit)) => {
+            let limit = util::timing::format_duration(limit);
+            println!("{}:\nparsing timed out after {}", puzzle, limit);
+            broken.push(puzzle);
+        }
+        Ok(Outcome::Done(Ok(answers))) => {
+            println!("{}:", puzzle);
+            answers.print();
+            timings.push((puzzle.to_string(), answers.timings));
+            if let Some(usage) = answers.memory {
+                memory.push((puzzle.to_string(), usage));
+            }
+
+            // There's nothing to record or verify unless every part got an answer
+            let parts = [Some(&answers.part1), answers.part2.as_ref()];
+            if parts.iter().flatten().any(|part| part.done().is_none()) {
+                broken.push(puzzle);
+                return;
+            }
+            let answers = parts.map(|part| part.and_then(Outcome::done).cloned());
+            let dir = puzzle_dir(puzzle);
+            if let Err(e) = util::check_ledger(&answers, source, &dir, record, verify) {
+                eprintln!("{}", e);
+                failures.push(puzzle);
+            }
+        }
+    });
+
+    if timings.len() > 1 {
+        println!();
+        print!("{}", util::timing::summary(&timings));
+    }
+    if memory.len() > 1 {
+        println!();
+        print!("{}", util::alloc::summary(&memory));
+    }
+
+    let names = |puzzles: &[Puzzle]| {
+        let names = puzzles.iter().map(Puzzle::to_string).collect::<Vec<_>>();
+        names.join(", ")
+    };
+    if !broken.is_empty() {
+        eprintln!("\nFAILED to solve {}", names(&broken));
+    }
+    if !failures.is_empty() {
+        eprintln!("\nFAILED to verify {}", names(&failures));
+    }
+    if !broken.is_empty() || !failures.is_empty() {
+        exit(1);
+    }
+}
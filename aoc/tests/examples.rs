@@ -0,0 +1,55 @@
+use libtest_mimic::{Arguments, Failed, Trial};
+use util::{Example, InputSource, Ledger, Puzzle, Solution};
+
+struct Check {
+    example: Example,
+    part: u8,
+}
+
+impl aoc::Visitor for Check {
+    type Output = Result<(), Failed>;
+
+    fn visit<S: Solution>(self, _puzzle: Puzzle, solution: S) -> Self::Output {
+        util::check_example(&solution, &self.example, self.part).map_err(Failed::from)
+    }
+}
+
+// Real inputs aren't committed, so these only run where both the input and its answers exist.
+fn ledger_example(puzzle: Puzzle) -> Option<Example> {
+    let dir = aoc::puzzle_dir(puzzle);
+    let source = InputSource::from_env();
+    let ledger = Ledger::load(&source, &dir).ok()?;
+    let path = source.path_in(&dir)?;
+    let input = std::fs::read_to_string(&path).ok()?;
+    let name = ledger.path.file_name()?.to_string_lossy().into_owned();
+    Some(Example {
+        name,
+        path,
+        input,
+        part1: ledger.part1,
+        part2: ledger.part2,
+    })
+}
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let mut trials = vec![];
+    for &puzzle in aoc::PUZZLES {
+        let examples = util::load_examples(&aoc::puzzle_dir(puzzle));
+        for example in examples.into_iter().chain(ledger_example(puzzle)) {
+            for part in [1, 2] {
+                if example.expected(part).is_none() {
+                    continue;
+                }
+                let name = format!("{}::{}::part{}", puzzle, example.name, part);
+                let example = example.clone();
+                trials.push(Trial::test(name, move || {
+                    aoc::visit(puzzle, Check { example, part })
+                }));
+            }
+        }
+    }
+
+    libtest_mimic::run(&args, trials).exit();
+}
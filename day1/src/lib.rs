@@ -0,0 +1,36 @@
+use std::fmt::Write;
+
+use util::generate::Rng;
+
+/// How many depths are deeper than the one `N` before. Comparing sliding windows of `N` only
+/// depends on the depths that enter and leave them, so this needs just the last `N`.
+fn increases<const N: usize>(depths: impl Iterator<Item = u16>) -> usize {
+    let mut window = [0; N];
+    let mut count = 0;
+    for (i, depth) in depths.enumerate() {
+        let old = std::mem::replace(&mut window[i % N], depth);
+        count += (i >= N && depth > old) as usize;
+    }
+    count
+}
+
+fn part1(depths: impl Iterator<Item = u16>) -> usize {
+    increases::<1>(depths)
+}
+
+fn part2(depths: impl Iterator<Item = u16>) -> usize {
+    increases::<3>(depths)
+}
+
+/// `size` depths, mostly getting deeper, like a real sonar sweep.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut depth = rng.range(100..=200);
+    for _ in 0..size {
+        writeln!(out, "{}", depth).unwrap();
+        depth = (depth + rng.range(-10..=20)).clamp(0, u16::MAX as i64);
+    }
+    out
+}
+
+util::register_stream!(try util::parse::<u16>, part1, part2; generate = generate);
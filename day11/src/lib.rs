@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use util::viz::{self, Frame};
+use util::Grid;
+
+type Input = Grid<u8>;
+type Output = usize;
+
+fn parse(s: &str) -> Input {
+    Grid::parse(s, |c| c as u8 - b'0')
+}
+
+fn simulate(state: &mut Input) -> usize {
+    let mut flashed = HashSet::new();
+    let mut flash_stack = vec![];
+    for (pos, v) in state.iter_mut() {
+        *v += 1;
+        if *v > 9 {
+            flash_stack.push(pos);
+        }
+    }
+
+    while let Some(pos) = flash_stack.pop() {
+        if flashed.contains(&pos) {
+            continue;
+        }
+        flashed.insert(pos);
+        for n in state.oct_neighbors(pos).collect::<Vec<_>>() {
+            state[n] += 1;
+            if state[n] > 9 {
+                flash_stack.push(n);
+            }
+        }
+    }
+
+    let count = flashed.len();
+    for pos in flashed {
+        state[pos] = 0;
+    }
+    count
+}
+
+fn part1(inp: &Input) -> Output {
+    let mut total = 0;
+    let mut state = inp.clone();
+    for _ in 0..100 {
+        total += simulate(&mut state);
+    }
+    total
+}
+
+fn part2(inp: &Input) -> Output {
+    let mut state = inp.clone();
+    let num_octopodes = state.len();
+
+    for i in 1.. {
+        if simulate(&mut state) == num_octopodes {
+            return i;
+        }
+    }
+    panic!("oh no");
+}
+
+fn frame(state: &Input) -> Frame {
+    state.map(|&energy| match energy {
+        0 => [255, 250, 200],
+        _ => viz::blend([10, 10, 40], [40, 110, 200], energy as f64 / 9.0),
+    })
+}
+
+/// Every step, up to the first one where they all flash at once.
+fn frames(inp: &Input) -> impl Iterator<Item = Frame> {
+    let mut state = inp.clone();
+    let mut synchronized = false;
+    let steps = std::iter::from_fn(move || {
+        if synchronized {
+            return None;
+        }
+        synchronized = simulate(&mut state) == state.len();
+        Some(frame(&state))
+    });
+    std::iter::once(frame(inp)).chain(steps)
+}
+
+util::register_alt!(parse, part1, part2; viz = frames);
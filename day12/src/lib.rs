@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use util::generate::Rng;
+use util::{scan, ParseError};
+
+/// Tunnels out of each cave, by id. `start` and `end` are always 0 and 1.
+struct Caves {
+    tunnels: Vec<Vec<usize>>,
+    small: Vec<bool>,
+}
+type Output = usize;
+
+const START: usize = 0;
+const END: usize = 1;
+
+fn is_lowercase(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+fn parse(s: &str) -> Result<Caves, ParseError> {
+    let mut ids = HashMap::from([("start", START), ("end", END)]);
+    let mut caves = Caves {
+        tunnels: vec![vec![]; 2],
+        small: vec![true; 2],
+    };
+    for (n, line) in (scan::Section { text: s, line: 1 }).lines() {
+        let line = line.trim();
+        let Some((a, b)) = line.split_once('-') else {
+            return Err(ParseError::whole(line, "expected a tunnel").with_line(n));
+        };
+        let [a, b] = [a, b].map(|name| match ids.get(name) {
+            Some(&id) => Ok(id),
+            // Which caves a path has visited are the bits of a u64
+            None if ids.len() == 64 => {
+                Err(ParseError::at(line, name, "more than 64 caves").with_line(n))
+            }
+            None => {
+                ids.insert(name, ids.len());
+                caves.tunnels.push(vec![]);
+                caves.small.push(is_lowercase(name));
+                Ok(ids.len() - 1)
+            }
+        });
+        let (a, b) = (a?, b?);
+        caves.tunnels[a].push(b);
+        caves.tunnels[b].push(a);
+    }
+    Ok(caves)
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Path {
+    location: usize,
+    visited: u64,
+    can_repeat: bool,
+}
+
+fn run(caves: &Caves, can_repeat: bool) -> Output {
+    let neighbors = |path: &Path| {
+        let mut next = vec![];
+        for &dst in &caves.tunnels[path.location] {
+            let bit = 1 << dst;
+            if dst == START {
+                continue;
+            } else if !caves.small[dst] || path.visited & bit == 0 {
+                next.push(Path {
+                    location: dst,
+                    visited: path.visited | bit,
+                    ..*path
+                });
+            } else if path.can_repeat {
+                next.push(Path {
+                    location: dst,
+                    can_repeat: false,
+                    ..*path
+                });
+            }
+        }
+        next
+    };
+
+    let start = Path {
+        location: START,
+        visited: 1 << START,
+        can_repeat,
+    };
+    util::search::count_paths(start, neighbors, |path| path.location == END)
+}
+
+fn part1(input: &Caves) -> Output {
+    run(input, false)
+}
+
+fn part2(input: &Caves) -> Output {
+    run(input, true)
+}
+
+/// Rooms of a big cave and a few small ones, like a real input, which `size` tunnels from `start`
+/// lead into. Paths can't get from one room to another without going through `start` or `end`, so
+/// the number of them grows with the tunnels rather than exploding the way it would with more
+/// caves, which can't be more than 64 anyway. Some tunnels are the same as others.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const ROOMS: usize = 12;
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng, big: bool| loop {
+        let name = (0..2)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        let name = if big { name.to_uppercase() } else { name };
+        if names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let mut tunnels = vec![];
+    let mut rooms = vec![];
+    for _ in 0..size.clamp(1, ROOMS) {
+        let big = name(rng, true);
+        let small = [(); 3].map(|()| name(rng, false));
+        for (i, a) in small.iter().enumerate() {
+            tunnels.push((big.clone(), a.clone()));
+            for b in &small[i + 1..] {
+                if rng.chance(0.5) {
+                    tunnels.push((a.clone(), b.clone()));
+                }
+            }
+        }
+        let exit = if rng.chance(0.5) {
+            &big
+        } else {
+            rng.choose(&small)
+        };
+        tunnels.push((exit.clone(), "end".to_owned()));
+        rooms.push(small);
+    }
+    for i in 0..size.max(1) {
+        let entrance = rng.choose(&rooms[i % rooms.len()]);
+        tunnels.push(("start".to_owned(), entrance.clone()));
+    }
+
+    rng.shuffle(&mut tunnels);
+    let mut out = String::new();
+    for (a, b) in tunnels {
+        let (a, b) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+        writeln!(out, "{}-{}", a, b).unwrap();
+    }
+    out
+}
+
+util::register_alt!(try parse, part1, part2; generate = generate);
@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+use util::geom::P2;
+use util::viz::{Frame, Pixel, Visualize};
+use util::{scan, ParseError};
+
+struct Input {
+    dots: Vec<P2<i32>>,
+    /// The axis to fold along, 0 for x or 1 for y, and where.
+    folds: Vec<(usize, i32)>,
+}
+
+type Output = usize;
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    let [dots, folds] = scan::sections(s)[..] else {
+        return Err(ParseError::whole(s, "expected dots, then folds"));
+    };
+    let dots = dots
+        .scan_lines::<(i32, i32)>("{},{}")?
+        .into_iter()
+        .map(P2::from)
+        .collect();
+    let folds = folds
+        .scan_lines::<(char, i32)>("fold along {}={}")?
+        .into_iter()
+        .map(|(axis, coord)| ((axis == 'y') as usize, coord))
+        .collect();
+    Ok(Input { dots, folds })
+}
+
+#[derive(Default)]
+struct Grid(BTreeSet<P2<i32>>);
+
+impl Grid {
+    fn from_input(inp: &Input) -> Self {
+        let mut g = Self::default();
+        g.0.extend(&inp.dots);
+        g
+    }
+
+    fn fold(&self, axis: usize, coord: i32) -> Self {
+        let mut g = Self::default();
+        for &(mut p) in &self.0 {
+            if p[axis] > coord {
+                p[axis] = coord - (p[axis] - coord);
+            }
+            g.0.insert(p);
+        }
+        g
+    }
+}
+
+impl Visualize for Grid {
+    fn frame(&self) -> Frame {
+        let width = self.0.iter().map(|p| p.x() + 1).max().unwrap_or(0);
+        let height = self.0.iter().map(|p| p.y() + 1).max().unwrap_or(0);
+        util::Grid::from_fn(width as usize, height as usize, |(x, y)| {
+            self.0.contains(&P2::new([x as i32, y as i32])).color()
+        })
+    }
+}
+
+/// The paper before and after each fold.
+fn frames(inp: &Input) -> impl Iterator<Item = Grid> {
+    let mut folds = inp.folds.clone().into_iter();
+    std::iter::successors(Some(Grid::from_input(inp)), move |grid| {
+        let (axis, coord) = folds.next()?;
+        Some(grid.fold(axis, coord))
+    })
+}
+
+fn part1(inp: &Input) -> Output {
+    let grid = Grid::from_input(inp);
+    let &(axis, coord) = &inp.folds[0];
+    let new_grid = grid.fold(axis, coord);
+
+    new_grid.0.len()
+}
+
+fn part2(inp: &Input) -> String {
+    let mut grid = Grid::from_input(inp);
+    for &(axis, coord) in &inp.folds {
+        grid = grid.fold(axis, coord);
+    }
+
+    let x_min = grid.0.iter().map(|p| p.x()).min().unwrap();
+    let y_min = grid.0.iter().map(|p| p.y()).min().unwrap();
+    let x_max = grid.0.iter().map(|p| p.x()).max().unwrap();
+    let y_max = grid.0.iter().map(|p| p.y()).max().unwrap();
+
+    let mut art = String::new();
+    for y in y_min..=y_max {
+        for x in x_min..=x_max {
+            art.push(if grid.0.contains(&P2::new([x, y])) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        art.push('\n');
+    }
+
+    // Whatever can't be read, like the example's square, is left for a person to read.
+    util::ocr::read_points(grid.0.iter().map(|p| (p.x(), p.y()))).unwrap_or(art)
+}
+
+util::register_alt!(try parse, part1, part2; viz = frames);
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use util::alt::Alternative;
+use util::generate::Rng;
+use util::{scan, ParseError};
+
+type Rules = HashMap<(u8, u8), u8>;
+
+struct Input {
+    template: Vec<u8>,
+    rules: Rules,
+}
+
+type Output = u64;
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    let [template, rules] = scan::sections(s)[..] else {
+        return Err(ParseError::whole(s, "expected a template, then rules"));
+    };
+    let template = template.scan::<String>("{}")?.into_bytes();
+    let rules = rules
+        .lines()
+        .map(|(n, line)| {
+            let line = line.trim();
+            let (pair, c) =
+                scan::scan::<(String, char)>(line, "{} -> {}").map_err(|e| e.with_line(n))?;
+            match *pair.as_bytes() {
+                [a, b] => Ok(((a, b), c as u8)),
+                _ => Err(ParseError::at(line, &line[..pair.len()], "expected a pair").with_line(n)),
+            }
+        })
+        .collect::<Result<_, ParseError>>()?;
+    Ok(Input { template, rules })
+}
+
+#[derive(Default)]
+struct State {
+    pair_counts: HashMap<(u8, u8), u64>,
+    char_counts: HashMap<u8, u64>,
+}
+
+impl State {
+    fn new(template: &[u8]) -> Self {
+        let mut state = Self::default();
+        for &[a, b] in template.array_windows() {
+            state.insert_pair(a, b, 1);
+        }
+        for &c in template {
+            state.insert_char(c, 1);
+        }
+        state
+    }
+
+    fn apply(&mut self, rules: &Rules) {
+        for ((a, b), n) in self.pair_counts.clone() {
+            if let Some(&c) = rules.get(&(a, b)) {
+                self.remove_pair(a, b, n);
+                self.insert_pair(a, c, n);
+                self.insert_pair(c, b, n);
+                self.insert_char(c, n);
+            } else {
+                println!("no insertion"); // this never happens
+            }
+        }
+    }
+
+    fn remove_pair(&mut self, a: u8, b: u8, n: u64) {
+        *self.pair_counts.get_mut(&(a, b)).unwrap() -= n;
+    }
+
+    fn insert_pair(&mut self, a: u8, b: u8, n: u64) {
+        *self.pair_counts.entry((a, b)).or_default() += n;
+    }
+
+    fn insert_char(&mut self, a: u8, n: u64) {
+        *self.char_counts.entry(a).or_default() += n;
+    }
+
+    fn result(&self) -> u64 {
+        let max = self.char_counts.values().max().unwrap();
+        let min = self.char_counts.values().min().unwrap();
+        max - min
+    }
+}
+
+fn run(inp: &Input, steps: u8) -> Output {
+    let mut state = State::new(&inp.template);
+    for _ in 0..steps {
+        state.apply(&inp.rules);
+    }
+    state.result()
+}
+
+fn part1(inp: &Input) -> Output {
+    run(inp, 10)
+}
+
+fn part2(inp: &Input) -> Output {
+    run(inp, 40)
+}
+
+/// Actually builds the polymer, which is fine for 10 steps but not for 40.
+fn expand(inp: &Input) -> String {
+    let mut polymer = inp.template.clone();
+    for _ in 0..10 {
+        let mut next = Vec::with_capacity(polymer.len() * 2);
+        for &[a, b] in polymer.array_windows() {
+            next.push(a);
+            next.extend(inp.rules.get(&(a, b)));
+        }
+        next.extend(polymer.last());
+        polymer = next;
+    }
+    let mut counts = HashMap::<u8, u64>::new();
+    for c in polymer {
+        *counts.entry(c).or_default() += 1;
+    }
+    let max = counts.values().max().unwrap();
+    let min = counts.values().min().unwrap();
+    (max - min).to_string()
+}
+
+const ALTERNATIVES: &[Alternative<Input>] = &[Alternative::new(1, "literal expansion", expand)];
+
+/// A template `size` elements long, with a rule for every pair of the 10 elements, like a real
+/// input.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let elements = b"BCFHKNOPSV";
+    let template = (0..size.max(2))
+        .map(|_| *rng.choose(elements) as char)
+        .collect::<String>();
+    let mut out = format!("{}\n\n", template);
+    for &a in elements {
+        for &b in elements {
+            let c = *rng.choose(elements);
+            writeln!(out, "{}{} -> {}", a as char, b as char, c as char).unwrap();
+        }
+    }
+    out
+}
+
+util::register_alt!(try parse, part1, part2; generate = generate; alt = ALTERNATIVES);
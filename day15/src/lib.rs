@@ -0,0 +1,64 @@
+use util::check::{self, Invariant};
+use util::generate::Rng;
+use util::{Grid, ParseError};
+
+type Input = Grid<u8>;
+type Output = u64;
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    Grid::try_parse(s, |c| {
+        c.to_digit(10).map(|d| d as u8).ok_or("expected a digit")
+    })
+}
+
+const INVARIANTS: &[Invariant<Input>] = &[
+    Invariant::new("the cave isn't empty", |input| {
+        check::ensure(input.width() > 0 && input.height() > 0, || {
+            "the cave is empty".to_owned()
+        })
+    }),
+    Invariant::new("every risk level is from 1 to 9", |input| {
+        match input.iter().find(|&(_, &risk)| risk == 0) {
+            Some(((x, y), _)) => Err(format!("the risk at ({}, {}) is 0", x, y)),
+            None => Ok(()),
+        }
+    }),
+];
+
+fn lowest_risk(costs: &Grid<u8>) -> Output {
+    let goal = (costs.width() - 1, costs.height() - 1);
+    let neighbors = |&p: &_| costs.quad_neighbors(p).map(|n| (n, costs[n] as u64));
+    util::search::dijkstra((0, 0), neighbors, |&p| p == goal)
+        .unwrap()
+        .cost
+}
+
+fn part1(costs: &Input) -> Output {
+    lowest_risk(costs)
+}
+
+fn part2(inp: &Input) -> Output {
+    let wrap = |x| match x {
+        0..=9 => x,
+        10.. => x - 9,
+    };
+
+    let (w, h) = (inp.width(), inp.height());
+    let costs = Grid::from_fn(w * 5, h * 5, |(x, y)| {
+        let (xi, yi) = ((x / w) as u8, (y / h) as u8);
+        wrap(inp[(x % w, y % h)] + xi + yi)
+    });
+
+    lowest_risk(&costs)
+}
+
+/// About `size` risk levels, in a square.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = ((size as f64).sqrt().round() as usize).max(1);
+    let grid = Grid::from_fn(side, side, |_| rng.range(1..=9) as u8);
+    grid.rows()
+        .map(|row| row.iter().map(|risk| risk.to_string()).collect::<String>() + "\n")
+        .collect()
+}
+
+util::register_alt!(try parse, part1, part2; check = INVARIANTS; generate = generate);
@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::Add;
+
+use util::generate::Rng;
+use util::geom::{Rotation, P3};
+use util::{scan, ParseError};
+use uuid::Uuid;
+
+type Input = Vec<Scanner>;
+type Output = usize;
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    let mut scanners = vec![];
+    for section in scan::sections(s) {
+        let mut lines = section.lines();
+        let (n, header) = lines.next().unwrap();
+        scan::scan::<u32>(header, "--- scanner {} ---").map_err(|e| e.with_line(n))?;
+
+        let mut scanner = Scanner::new();
+        for (n, line) in lines {
+            let (x, y, z) = scan::scan(line, "{},{},{}").map_err(|e| e.with_line(n))?;
+            scanner.beacons.push(P3::new([x, y, z]));
+        }
+        scanners.push(scanner);
+    }
+    Ok(scanners)
+}
+
+type Beacon = P3<i32>;
+
+#[derive(Debug, Clone)]
+struct Scanner {
+    beacons: Vec<Beacon>,
+    id: Uuid,
+}
+
+impl Scanner {
+    fn new() -> Self {
+        Self {
+            beacons: vec![],
+            id: Uuid::new_v4(),
+        }
+    }
+
+    fn rotated(&self, rotation: Rotation) -> Self {
+        Self {
+            beacons: self.beacons.iter().map(|&b| rotation * b).collect(),
+            id: self.id,
+        }
+    }
+
+    fn all_rotations(&self) -> [Self; 24] {
+        Rotation::all().map(|r| self.rotated(r))
+    }
+
+    fn overlaps(&self, other: &Self, offset: P3<i32>) -> bool {
+        let mut hit_count = 0;
+        let mut remaining = self.beacons.len();
+
+        for b in &self.beacons {
+            let b = *b + offset;
+            if other.beacons.contains(&b) {
+                hit_count += 1;
+                if hit_count >= 12 {
+                    return true;
+                }
+            }
+
+            remaining -= 1;
+            if hit_count + remaining < 12 {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+impl Add<P3<i32>> for Scanner {
+    type Output = Self;
+    fn add(mut self, rhs: P3<i32>) -> Self::Output {
+        for beacon in &mut self.beacons {
+            *beacon += rhs;
+        }
+        self
+    }
+}
+
+fn unify(inp: &Input) -> (Vec<Scanner>, Vec<P3<i32>>) {
+    let mut region = vec![inp[0].clone()];
+
+    let mut scanners = inp[1..].to_vec();
+    let mut offsets = vec![];
+    let mut failures = HashSet::new();
+
+    while !scanners.is_empty() {
+        let scanner = scanners.remove(0);
+        let mut matching_scanner = None;
+
+        'found: for test_scanner in &region {
+            if failures.contains(&(test_scanner.id, scanner.id)) {
+                continue;
+            }
+
+            for rotated in scanner.all_rotations() {
+                for test_beacon in &test_scanner.beacons {
+                    for comparison_beacon in &rotated.beacons {
+                        let delta = *test_beacon - *comparison_beacon;
+                        if rotated.overlaps(test_scanner, delta) {
+                            let translated = rotated.clone() + delta;
+                            matching_scanner = Some((translated, delta));
+                            break 'found;
+                        }
+                    }
+                }
+            }
+
+            failures.insert((test_scanner.id, scanner.id));
+        }
+
+        if let Some((s, delta)) = matching_scanner {
+            region.push(s);
+            offsets.push(delta);
+        } else {
+            // better luck next time
+            scanners.push(scanner);
+        }
+    }
+
+    (region, offsets)
+}
+
+fn part1(inp: &Input) -> Output {
+    let (region, _) = unify(inp);
+
+    region
+        .into_iter()
+        .flat_map(|s| s.beacons)
+        .collect::<HashSet<Beacon>>()
+        .len()
+}
+
+fn part2(inp: &Input) -> Output {
+    let (_, offsets) = unify(inp);
+
+    let mut max_dist = 0;
+    for a in &offsets {
+        for b in &offsets {
+            max_dist = max_dist.max(a.manhattan(*b));
+        }
+    }
+    max_dist as _
+}
+
+/// `size` scanners, each facing any of the 24 ways and sharing at least 12 beacons with one placed
+/// before it, so that they can all be pieced together.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const RANGE: i64 = 1000;
+    let mut positions = vec![];
+    let mut beacons = vec![];
+    let mut seen = HashSet::new();
+    let mut scatter = |rng: &mut Rng, lo: [i64; 3], hi: [i64; 3], n: usize| {
+        let mut added = 0;
+        while added < n {
+            let beacon = P3::new([0, 1, 2].map(|i| rng.range(lo[i]..=hi[i]) as i32));
+            if seen.insert(beacon) {
+                beacons.push(beacon);
+                added += 1;
+            }
+        }
+    };
+
+    for i in 0..size {
+        let parent = (i > 0).then(|| *rng.choose(&positions));
+        let pos = parent.map_or([0; 3], |p: [i64; 3]| p.map(|c| c + rng.range(-1100..=1100)));
+        scatter(rng, pos.map(|c| c - RANGE), pos.map(|c| c + RANGE), 13);
+        if let Some(parent) = parent {
+            // Enough where the two overlap for them to be matched up
+            let lo = [0, 1, 2].map(|a| pos[a].max(parent[a]) - RANGE);
+            let hi = [0, 1, 2].map(|a| pos[a].min(parent[a]) + RANGE);
+            scatter(rng, lo, hi, 12);
+        }
+        positions.push(pos);
+    }
+
+    let mut out = String::new();
+    for (i, pos) in positions.iter().enumerate() {
+        let rotation = *rng.choose(&Rotation::all());
+        let mut seen = beacons
+            .iter()
+            .map(|b| [0, 1, 2].map(|i| b[i] as i64 - pos[i]))
+            .filter(|d| d.iter().all(|c| c.abs() <= RANGE))
+            .map(|d| rotation * P3::new(d.map(|c| c as i32)))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut seen);
+
+        if i > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "--- scanner {} ---", i).unwrap();
+        for b in seen {
+            writeln!(out, "{}", b).unwrap();
+        }
+    }
+    out
+}
+
+util::register_alt!(try parse, part1, part2; generate = generate);
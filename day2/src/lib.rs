@@ -0,0 +1,66 @@
+use std::str::FromStr;
+use util::ParseError;
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Forward,
+    Down,
+    Up,
+}
+
+#[derive(Clone, Copy)]
+struct Movement {
+    direction: Direction,
+    amount: i32,
+}
+
+impl FromStr for Movement {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (l, r) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::whole(s, "expected `<direction> <amount>`"))?;
+        let direction = match l {
+            "forward" => Direction::Forward,
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            _ => return Err(ParseError::at(s, l, "unknown direction")),
+        };
+        let amount = r
+            .parse()
+            .map_err(|_| ParseError::at(s, r, "invalid amount"))?;
+        Ok(Self { direction, amount })
+    }
+}
+
+fn part1(input: impl Iterator<Item = Movement>) -> usize {
+    let mut x = 0;
+    let mut y = 0;
+    for m in input {
+        match m.direction {
+            Direction::Forward => x += m.amount,
+            Direction::Down => y += m.amount,
+            Direction::Up => y -= m.amount,
+        }
+    }
+    (x * y) as usize
+}
+
+fn part2(input: impl Iterator<Item = Movement>) -> usize {
+    let mut x = 0;
+    let mut y = 0;
+    let mut aim = 0;
+    for m in input {
+        match m.direction {
+            Direction::Down => aim += m.amount,
+            Direction::Up => aim -= m.amount,
+            Direction::Forward => {
+                x += m.amount;
+                y += m.amount * aim;
+            }
+        }
+    }
+    (x * y) as usize
+}
+
+util::register_stream!(try Movement::from_str, part1, part2);
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use util::viz::{Frame, Pixel, Visualize};
+use util::{scan, Grid, ParseError};
+
+struct Algorithm([bool; 512]);
+
+type Input = (Algorithm, Grid<bool>);
+type Output = usize;
+
+fn pixel(c: char) -> Result<bool, &'static str> {
+    match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err("expected `#` or `.`"),
+    }
+}
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    let [algorithm, image] = scan::sections(s)[..] else {
+        return Err(ParseError::whole(
+            s,
+            "expected the algorithm, then the image",
+        ));
+    };
+
+    let bits = algorithm
+        .lines()
+        .flat_map(|(_, line)| line.trim().chars())
+        .map(pixel)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ParseError::whole(algorithm.text, e).with_line(algorithm.line))?;
+    let algorithm = bits.try_into().map(Algorithm).map_err(|bits: Vec<_>| {
+        ParseError::whole(
+            algorithm.text,
+            format!("expected 512 pixels, found {}", bits.len()),
+        )
+        .with_line(algorithm.line)
+    })?;
+
+    let image = Grid::try_parse(image.text, pixel).map_err(|e| {
+        let line = image.line + e.line.unwrap_or(1) - 1;
+        e.with_line(line)
+    })?;
+
+    Ok((algorithm, image))
+}
+
+struct Image {
+    top: i32,
+    left: i32,
+    bottom: i32,
+    right: i32,
+    border: bool,
+    pixels: HashSet<(i32, i32)>,
+}
+
+impl std::fmt::Display for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in self.top..self.bottom {
+            for x in self.left..self.right {
+                if self.pixels.contains(&(x, y)) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Image {
+    fn new(grid: &Grid<bool>) -> Self {
+        Image {
+            top: 0,
+            left: 0,
+            bottom: grid.height() as i32,
+            right: grid.width() as i32,
+            border: false,
+            pixels: grid
+                .iter()
+                .filter(|&(_, &lit)| lit)
+                .map(|((x, y), _)| (x as i32, y as i32))
+                .collect(),
+        }
+    }
+
+    fn contains(&self, (x, y): (i32, i32)) -> bool {
+        if x < self.left || x >= self.right || y < self.top || y >= self.bottom {
+            self.border
+        } else {
+            self.pixels.contains(&(x, y))
+        }
+    }
+
+    fn enhance(&self, algorithm: &Algorithm) -> Self {
+        let mut new = Image {
+            top: self.top - 1,
+            left: self.left - 1,
+            bottom: self.bottom + 1,
+            right: self.right + 1,
+            border: algorithm.0[0] ^ self.border,
+            pixels: HashSet::new(),
+        };
+
+        for y in new.top..new.bottom {
+            for x in new.left..new.right {
+                let coords = [
+                    (x - 1, y - 1),
+                    (x, y - 1),
+                    (x + 1, y - 1),
+                    (x - 1, y),
+                    (x, y),
+                    (x + 1, y),
+                    (x - 1, y + 1),
+                    (x, y + 1),
+                    (x + 1, y + 1),
+                ];
+                let mut index = 0;
+                for coord in coords {
+                    index = (index << 1) | self.contains(coord) as usize;
+                }
+                if algorithm.0[index] {
+                    new.pixels.insert((x, y));
+                }
+            }
+        }
+
+        new
+    }
+}
+
+impl Visualize for Image {
+    fn frame(&self) -> Frame {
+        let (width, height) = (self.right - self.left, self.bottom - self.top);
+        Grid::from_fn(width as usize, height as usize, |(x, y)| {
+            let p = (self.left + x as i32, self.top + y as i32);
+            self.pixels.contains(&p).color()
+        })
+    }
+}
+
+/// The image as it's enhanced 50 times.
+fn frames((algorithm, rows): &Input) -> impl Iterator<Item = Image> {
+    let algorithm = Algorithm(algorithm.0);
+    std::iter::successors(Some(Image::new(rows)), move |image| {
+        Some(image.enhance(&algorithm))
+    })
+    .take(51)
+}
+
+fn part1(inp: &Input) -> Output {
+    let (ref algorithm, ref rows) = inp;
+
+    let mut image = Image::new(rows);
+
+    for _ in 0..2 {
+        image = image.enhance(algorithm);
+    }
+
+    image.pixels.len()
+}
+
+fn part2(inp: &Input) -> Output {
+    let (ref algorithm, ref rows) = inp;
+
+    let mut image = Image::new(rows);
+
+    for _ in 0..50 {
+        image = image.enhance(algorithm);
+    }
+
+    image.pixels.len()
+}
+
+util::register_alt!(try parse, part1, part2; viz = frames);
@@ -0,0 +1,238 @@
+use fnv::FnvHashMap as HashMap;
+use std::str::FromStr;
+use util::check::{self, Invariant};
+use util::scan::scan;
+use util::ParseError;
+
+#[derive(Copy, Clone)]
+enum Var {
+    X,
+    Y,
+    Z,
+    W,
+}
+
+impl std::str::FromStr for Var {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = match s {
+            "x" => Var::X,
+            "y" => Var::Y,
+            "z" => Var::Z,
+            "w" => Var::W,
+            _ => return Err(ParseError::whole(s, "expected a register")),
+        };
+        Ok(v)
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Rval {
+    Var(Var),
+    Imm(i32),
+}
+
+impl FromStr for Rval {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = if let Ok(n) = s.parse::<i32>() {
+            Rval::Imm(n)
+        } else {
+            Rval::Var(
+                s.parse()
+                    .map_err(|_| ParseError::whole(s, "expected a register or an integer"))?,
+            )
+        };
+        Ok(v)
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Op {
+    Inp(Var),
+    Add(Var, Rval),
+    Mul(Var, Rval),
+    Div(Var, Rval),
+    Mod(Var, Rval),
+    Eql(Var, Rval),
+}
+
+impl Op {
+    fn dest(&self) -> Var {
+        use Op::*;
+        match self {
+            Inp(v) | Add(v, _) | Mul(v, _) | Div(v, _) | Mod(v, _) | Eql(v, _) => *v,
+        }
+    }
+
+    fn val(&self) -> Rval {
+        use Op::*;
+        match self {
+            Add(_, v) | Mul(_, v) | Div(_, v) | Mod(_, v) | Eql(_, v) => *v,
+            Inp(_) => panic!(),
+        }
+    }
+}
+
+type Input = Op;
+type Output = u128;
+
+util::scannable!(Var, Rval);
+
+impl FromStr for Op {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (opcode, args) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::whole(s, "missing destination register"))?;
+        let op: fn(Var, Rval) -> Op = match opcode {
+            "inp" => return scan(args, "{}").map(Op::Inp).map_err(|e| e.within(s, args)),
+            "add" => Op::Add,
+            "mul" => Op::Mul,
+            "div" => Op::Div,
+            "mod" => Op::Mod,
+            "eql" => Op::Eql,
+            _ => return Err(ParseError::at(s, opcode, "unknown instruction")),
+        };
+        let (var, val) = scan(args, "{} {}").map_err(|e| e.within(s, args))?;
+        Ok(op(var, val))
+    }
+}
+
+#[derive(Default, Copy, Clone)]
+struct Machine<'a> {
+    x: i32,
+    y: i32,
+    z: i32,
+    w: i32,
+    code: &'a [Op],
+}
+
+impl std::cmp::PartialEq for Machine<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.z == other.z
+    }
+}
+impl std::cmp::Eq for Machine<'_> {}
+impl std::hash::Hash for Machine<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.z.hash(state)
+    }
+}
+
+impl<'a> Machine<'a> {
+    fn new(code: &'a [Op]) -> Self {
+        Self {
+            code,
+            ..Default::default()
+        }
+    }
+
+    fn reg(&mut self, v: Var) -> &mut i32 {
+        match v {
+            Var::X => &mut self.x,
+            Var::Y => &mut self.y,
+            Var::Z => &mut self.z,
+            Var::W => &mut self.w,
+        }
+    }
+
+    fn execute(&mut self, op: Op, input_val: &mut i32) {
+        let val = if let Op::Inp(_) = op {
+            std::mem::take(input_val)
+        } else {
+            match op.val() {
+                Rval::Var(v) => *self.reg(v),
+                Rval::Imm(v) => v,
+            }
+        };
+
+        let dest = self.reg(op.dest());
+        *dest = match op {
+            Op::Inp(..) => val,
+            Op::Add(..) => *dest + val,
+            Op::Mul(..) => *dest * val,
+            Op::Div(..) => *dest / val,
+            Op::Mod(..) => *dest % val,
+            Op::Eql(..) => (*dest == val) as i32,
+        };
+    }
+
+    fn run(&mut self, mut input_val: i32) {
+        while let Some(op) = self.code.first().copied() {
+            if matches!(op, Op::Inp(..)) && input_val == 0 {
+                // we need another input
+                break;
+            }
+            self.execute(op, &mut input_val);
+            self.code = &self.code[1..];
+        }
+    }
+}
+
+fn finalize(machine_input: &[i32]) -> u128 {
+    let mut n = 0;
+    for digit in machine_input {
+        n = (n * 10) + *digit as u128;
+    }
+    n
+}
+
+fn run(inp: &[Input], mut comparator: impl FnMut(Vec<i32>, Vec<i32>) -> Vec<i32>) -> u128 {
+    let mut states = HashMap::<Machine, Vec<i32>>::default();
+    states.insert(Machine::new(inp), vec![]);
+
+    for _ in 0..14 {
+        let mut new_states = HashMap::default();
+
+        for (machine, past_input) in states {
+            for digit in 1..=9 {
+                let mut new_input = past_input.clone();
+                new_input.push(digit);
+
+                let mut m = machine;
+                m.run(digit);
+
+                let entry = new_states.entry(m).or_insert(new_input.clone());
+                *entry = comparator(entry.clone(), new_input);
+            }
+        }
+
+        states = new_states;
+    }
+
+    for (machine, best_input) in states {
+        assert!(machine.code.is_empty());
+        if machine.z == 0 {
+            return finalize(&best_input);
+        }
+    }
+    panic!()
+}
+
+/// The search tries each digit for each `inp`, one at a time.
+const INVARIANTS: &[Invariant<[Input]>] = &[
+    Invariant::new("there are 14 `inp` instructions", |inp| {
+        let n = inp.iter().filter(|op| matches!(op, Op::Inp(..))).count();
+        check::count(n, 14, "`inp` instructions")
+    }),
+    Invariant::new("nothing is divided by zero", |inp| {
+        let zero = inp
+            .iter()
+            .position(|op| matches!(op, Op::Div(_, Rval::Imm(0)) | Op::Mod(_, Rval::Imm(0))));
+        match zero {
+            Some(i) => Err(format!("instruction {} divides by zero", i + 1)),
+            None => Ok(()),
+        }
+    }),
+];
+
+fn part1(inp: &[Input]) -> Output {
+    run(inp, std::cmp::max)
+}
+
+fn part2(inp: &[Input]) -> Output {
+    run(inp, std::cmp::min)
+}
+
+util::register!(try util::parse::<Op>, part1, part2; check = INVARIANTS);
@@ -0,0 +1,116 @@
+use util::viz::{Pixel, Rgb};
+use util::Grid;
+
+#[derive(PartialEq, Copy, Clone)]
+enum Space {
+    Empty,
+    East,
+    South,
+}
+
+impl std::fmt::Display for Space {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Space::Empty => '.',
+            Space::East => '>',
+            Space::South => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl Pixel for Space {
+    fn color(&self) -> Rgb {
+        match self {
+            Space::Empty => [10, 20, 50],
+            Space::East => [80, 200, 120],
+            Space::South => [230, 140, 60],
+        }
+    }
+}
+
+type Input = Grid<Space>;
+type Output = i32;
+
+fn parse(s: &str) -> Input {
+    Grid::parse(s, |c| match c {
+        '.' => Space::Empty,
+        '>' => Space::East,
+        'v' => Space::South,
+        _ => panic!(),
+    })
+}
+
+fn step(state: &Input) -> Input {
+    let (w, h) = (state.width(), state.height());
+
+    let mut state2 = Grid::new(w, h, Space::Empty);
+
+    for (pos, &v) in state.iter() {
+        if v == Space::South {
+            state2[pos] = Space::South;
+        }
+    }
+
+    for (pos, &v) in state.iter() {
+        if v != Space::East {
+            continue;
+        }
+        let dest = state.wrapping_offset(pos, (1, 0));
+
+        let new_pos = if state[dest] == Space::Empty {
+            dest
+        } else {
+            pos
+        };
+        state2[new_pos] = Space::East;
+    }
+
+    let mut state3 = Grid::new(w, h, Space::Empty);
+
+    for (pos, &v) in state2.iter() {
+        if v == Space::East {
+            state3[pos] = Space::East;
+        }
+    }
+
+    for (pos, &v) in state2.iter() {
+        if v != Space::South {
+            continue;
+        }
+        let dest = state2.wrapping_offset(pos, (0, 1));
+
+        let new_pos = if state2[dest] == Space::Empty {
+            dest
+        } else {
+            pos
+        };
+        state3[new_pos] = Space::South;
+    }
+
+    state3
+}
+
+fn part1(inp: &Input) -> Output {
+    let mut state = inp.clone();
+
+    for i in 1.. {
+        let new_state = step(&state);
+        if new_state == state {
+            return i;
+        }
+        state = new_state;
+    }
+
+    panic!()
+}
+
+/// Every step, until the sea cucumbers stop moving.
+fn frames(inp: &Input) -> impl Iterator<Item = Input> {
+    std::iter::successors(Some(inp.clone()), |state| {
+        let next = step(state);
+        (next != *state).then_some(next)
+    })
+}
+
+util::register_alt!(parse, part1; viz = frames);
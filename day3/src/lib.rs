@@ -0,0 +1,103 @@
+use util::check::{self, Invariant};
+use util::ParseError;
+
+fn parse_bits(bits: &[bool]) -> u32 {
+    let mut n = 0;
+    for bit in bits {
+        n <<= 1;
+        n |= *bit as u32;
+    }
+    n
+}
+
+fn parse(s: &str) -> Result<Vec<bool>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(ParseError::at(
+                s,
+                &s[i..i + c.len_utf8()],
+                "expected 0 or 1",
+            )),
+        })
+        .collect()
+}
+
+const INVARIANTS: &[Invariant<[Vec<bool>]>] = &[
+    Invariant::new("there are numbers", |input| {
+        check::non_empty(input, "number")
+    }),
+    Invariant::new("numbers are all the same width", |input| {
+        check::same_len(input, "number")
+    }),
+    Invariant::new("numbers fit in 32 bits", |input| {
+        let width = input.first().map_or(0, Vec::len);
+        check::ensure(width <= 32, || format!("numbers are {} bits wide", width))
+    }),
+    Invariant::new(
+        "numbers are distinct, so each rating is one number",
+        |input| {
+            let mut sorted = input.to_vec();
+            sorted.sort();
+            match sorted.windows(2).find(|w| w[0] == w[1]) {
+                Some(w) => Err(format!("{:b} appears more than once", parse_bits(&w[0]))),
+                None => Ok(()),
+            }
+        },
+    ),
+];
+
+fn part1(input: &[Vec<bool>]) -> u32 {
+    let mut gamma = vec![];
+    let mut epsilon = vec![];
+    for i in 0..input[0].len() {
+        let mut count = 0;
+        for num in input {
+            count += num[i] as usize;
+        }
+        let gamma_bit = count >= input.len() / 2;
+        let epsilon_bit = !gamma_bit;
+        gamma.push(gamma_bit);
+        epsilon.push(epsilon_bit);
+    }
+    let gamma_n = parse_bits(&gamma);
+    let epsilon_n = parse_bits(&epsilon);
+    gamma_n * epsilon_n
+}
+
+fn part2(input: &[Vec<bool>]) -> u32 {
+    let len = input[0].len();
+
+    let most_common_bit_in_pos = |i: usize, input: &[Vec<bool>]| {
+        let min_len = input.len() / 2 + input.len() % 2;
+        input.iter().map(|v| v[i] as usize).sum::<usize>() >= min_len
+    };
+
+    let least_common_bit_in_pos = |i: usize, input: &[Vec<bool>]| {
+        let max_len = input.len() / 2 + input.len() % 2;
+        input.iter().map(|v| v[i] as usize).sum::<usize>() < max_len
+    };
+
+    let mut o2_rating = input.to_vec();
+    let mut co2_rating = input.to_vec();
+    for i in 0..len {
+        let mcb = most_common_bit_in_pos(i, &o2_rating);
+        o2_rating.retain(|num| num[i] == mcb);
+        if o2_rating.len() == 1 {
+            break;
+        }
+    }
+    for i in 0..len {
+        let lcb = least_common_bit_in_pos(i, &co2_rating);
+        co2_rating.retain(|num| num[i] == lcb);
+        if co2_rating.len() == 1 {
+            break;
+        }
+    }
+    assert_eq!(o2_rating.len(), 1);
+    assert_eq!(co2_rating.len(), 1);
+    parse_bits(&co2_rating[0]) * parse_bits(&o2_rating[0])
+}
+
+util::register!(try parse, part1, part2; check = INVARIANTS);
@@ -0,0 +1,155 @@
+use std::fmt::{Display, Formatter};
+use util::check::{self, Invariant};
+use util::{scan, ParseError};
+
+#[derive(Default, Debug, Copy, Clone)]
+struct Board([[u8; 5]; 5]);
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..5 {
+            for x in 0..5 {
+                let n = self.0[y][x];
+                if n <= 9 {
+                    write!(f, " ")?;
+                }
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", n)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+struct BoardState([[bool; 5]; 5]);
+
+impl BoardState {
+    fn has_won(&self) -> bool {
+        for i in 0..5 {
+            if (0..5).all(|j| self.0[i][j]) || (0..5).all(|j| self.0[j][i]) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn mark(&mut self, board: &Board, n: u8) {
+        for y in 0..5 {
+            for x in 0..5 {
+                if board.0[y][x] == n {
+                    self.0[y][x] = true;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn sum_of_unmarked(&self, board: &Board) -> u32 {
+        let mut sum = 0;
+        for y in 0..5 {
+            for x in 0..5 {
+                if !self.0[y][x] {
+                    sum += board.0[y][x] as u32;
+                }
+            }
+        }
+        sum
+    }
+}
+
+fn parse_input(data: &str) -> Result<(Vec<u8>, Vec<Board>), ParseError> {
+    let sections = scan::sections(data);
+    let Some((calls, boards)) = sections.split_first() else {
+        return Err(ParseError::whole(
+            data,
+            "expected the calls, then the boards",
+        ));
+    };
+    let calls = calls.scan("{,}")?;
+
+    let boards =
+        boards
+            .iter()
+            .map(|section| {
+                let rows = section.scan_lines::<Vec<u8>>("{ }")?;
+                let rows = rows.into_iter().map(<[u8; 5]>::try_from);
+                match rows.collect::<Result<Vec<_>, _>>().map(<[_; 5]>::try_from) {
+                    Ok(Ok(board)) => Ok(Board(board)),
+                    _ => Err(ParseError::whole(section.text, "expected a 5x5 board")
+                        .with_line(section.line)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+    Ok((calls, boards))
+}
+
+type Input = (Vec<u8>, Vec<Board>);
+
+/// The first of `numbers` that appears again, if any.
+fn repeated(numbers: impl IntoIterator<Item = u8>) -> Option<u8> {
+    let mut seen = [false; 256];
+    numbers
+        .into_iter()
+        .find(|&n| std::mem::replace(&mut seen[n as usize], true))
+}
+
+const INVARIANTS: &[Invariant<Input>] = &[
+    Invariant::new("there are boards", |(_, boards)| {
+        check::non_empty(boards, "board")
+    }),
+    Invariant::new("no number is called twice", |(calls, _)| {
+        match repeated(calls.iter().copied()) {
+            Some(n) => Err(format!("{} is called more than once", n)),
+            None => Ok(()),
+        }
+    }),
+    Invariant::new("no board has a number twice", |(_, boards)| {
+        for (i, board) in boards.iter().enumerate() {
+            if let Some(n) = repeated(board.0.into_iter().flatten()) {
+                return Err(format!("board {} has {} more than once", i + 1, n));
+            }
+        }
+        Ok(())
+    }),
+];
+
+fn part1((calls, boards): &(Vec<u8>, Vec<Board>)) -> u32 {
+    let mut boards = boards.clone();
+    let mut states = vec![BoardState::default(); boards.len()];
+    for &call in calls {
+        for (state, board) in states.iter_mut().zip(&mut boards) {
+            state.mark(board, call);
+            if state.has_won() {
+                return state.sum_of_unmarked(board) * call as u32;
+            }
+        }
+    }
+    panic!("no winner");
+}
+
+fn part2((calls, boards): &(Vec<u8>, Vec<Board>)) -> u32 {
+    let mut boards = boards.clone();
+    let mut states = vec![BoardState::default(); boards.len()];
+    for &call in calls {
+        for (state, board) in states.iter_mut().zip(&mut boards) {
+            state.mark(board, call);
+        }
+        if states.len() == 1 && states[0].has_won() {
+            return states[0].sum_of_unmarked(&boards[0]) * call as u32;
+        }
+        for i in (0..states.len()).rev() {
+            if states[i].has_won() {
+                states.remove(i);
+                boards.remove(i);
+            }
+        }
+    }
+    panic!("no last-place");
+}
+
+util::register_alt!(try parse_input, part1, part2; check = INVARIANTS);
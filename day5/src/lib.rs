@@ -0,0 +1,121 @@
+use either::{Left, Right};
+use std::fmt::Write;
+use std::str::FromStr;
+use util::generate::Rng;
+use util::geom::P2;
+use util::ParseError;
+
+struct Line(P2<usize>, P2<usize>);
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (l, r) = s
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::whole(s, "expected `x1,y1 -> x2,y2`"))?;
+        let point = |p: &str| -> Result<P2<usize>, ParseError> {
+            let (x, y) = p
+                .split_once(",")
+                .ok_or_else(|| ParseError::at(s, p, "expected `x,y`"))?;
+            let coord = |c: &str| {
+                c.parse()
+                    .map_err(|_| ParseError::at(s, c, "invalid coordinate"))
+            };
+            Ok(P2::new([coord(x)?, coord(y)?]))
+        };
+        Ok(Line(point(l)?, point(r)?))
+    }
+}
+
+fn run(input: &[Line], diagonals: bool) -> usize {
+    let x_max = input
+        .iter()
+        .flat_map(|l| [l.0.x(), l.1.x()])
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let y_max = input
+        .iter()
+        .flat_map(|l| [l.0.y(), l.1.y()])
+        .max()
+        .unwrap_or_default()
+        + 1;
+
+    let range = |v1: usize, v2: usize| {
+        if v1 < v2 {
+            Left(v1..=v2)
+        } else {
+            Right((v2..=v1).rev())
+        }
+    };
+
+    let mut grid = vec![vec![0; x_max]; y_max];
+    for line in input {
+        if line.0.x() == line.1.x() {
+            let x = line.0.x();
+            for y in range(line.0.y(), line.1.y()) {
+                grid[y][x] += 1;
+            }
+        } else if line.0.y() == line.1.y() {
+            let y = line.0.y();
+            for x in range(line.0.x(), line.1.x()) {
+                grid[y][x] += 1;
+            }
+        } else if diagonals {
+            let ys = range(line.0.y(), line.1.y());
+            let xs = range(line.0.x(), line.1.x());
+            for (y, x) in ys.zip(xs) {
+                grid[y][x] += 1;
+            }
+        }
+    }
+
+    grid.iter().flatten().filter(|n| **n >= 2).count()
+}
+
+fn part1(input: &[Line]) -> usize {
+    run(input, false)
+}
+
+fn part2(input: &[Line]) -> usize {
+    run(input, true)
+}
+
+/// `size` horizontal, vertical and diagonal lines, on a floor twice as wide as there are lines,
+/// like a real input's 500 lines on a floor about 1000 wide.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const DIRECTIONS: [(i64, i64); 8] = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+    let max = (size as i64 * 2).max(2) - 1;
+    let mut out = String::new();
+    for _ in 0..size {
+        let (x, y) = (rng.range(0..=max), rng.range(0..=max));
+        // Up to half as long as the floor is wide, in a direction that has room for it
+        let room = |v: i64, d: i64| match d {
+            1 => max - v,
+            -1 => v,
+            _ => i64::MAX,
+        };
+        let ((dx, dy), len) = loop {
+            let (dx, dy) = *rng.choose(&DIRECTIONS);
+            let len = room(x, dx).min(room(y, dy)).min(size as i64);
+            if len > 0 {
+                break ((dx, dy), len);
+            }
+        };
+        let len = rng.range(1..=len);
+        writeln!(out, "{},{} -> {},{}", x, y, x + dx * len, y + dy * len).unwrap();
+    }
+    out
+}
+
+util::register!(try util::parse::<Line>, part1, part2; generate = generate);
@@ -0,0 +1,76 @@
+use util::alt::Alternative;
+use util::generate::Rng;
+
+type Input = Vec<i32>;
+type Output = i32;
+
+fn parse_input(s: &str) -> Input {
+    s.split(',').map(|t| t.parse().unwrap()).collect()
+}
+
+fn part1(input: &[Input]) -> Output {
+    let positions = input[0].clone();
+    let min = positions.iter().copied().min().unwrap();
+    let max = positions.iter().copied().max().unwrap();
+    (min..=max)
+        .map(|n| positions.iter().copied().map(|x| (x - n).abs()).sum())
+        .min()
+        .unwrap()
+}
+
+fn part2_cost(x: i32, n: i32) -> i32 {
+    let a = (x - n).abs();
+    a * (a + 1) / 2
+}
+
+fn part2(input: &[Input]) -> Output {
+    let positions = input[0].clone();
+    let min = positions.iter().copied().min().unwrap();
+    let max = positions.iter().copied().max().unwrap();
+    (min..=max)
+        .map(|n| positions.iter().copied().map(|x| part2_cost(x, n)).sum())
+        .min()
+        .unwrap()
+}
+
+/// The total distance is smallest at the median, since moving away from it gets further from at
+/// least as many crabs as it gets closer to.
+fn median(input: &[Input]) -> String {
+    let mut positions = input[0].clone();
+    positions.sort_unstable();
+    let n = positions[positions.len() / 2];
+    positions
+        .iter()
+        .map(|x| (x - n).abs())
+        .sum::<i32>()
+        .to_string()
+}
+
+/// The best position is within half a step of the mean, though it isn't always the one the mean
+/// rounds to.
+fn mean(input: &[Input]) -> String {
+    let positions = &input[0];
+    let sum = positions.iter().sum::<i32>();
+    let mean = sum.div_euclid(positions.len() as i32);
+    (mean..=mean + 1)
+        .map(|n| positions.iter().map(|&x| part2_cost(x, n)).sum::<i32>())
+        .min()
+        .unwrap()
+        .to_string()
+}
+
+const ALTERNATIVES: &[Alternative<[Input]>] = &[
+    Alternative::new(1, "median", median),
+    Alternative::new(2, "mean", mean),
+];
+
+/// `size` crabs, on one line like a real input, clustered towards the low positions.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = 2 * size as i64;
+    let positions = (0..size)
+        .map(|_| rng.range(0..=max).min(rng.range(0..=max)).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n", positions.join(","))
+}
+
+util::register!(parse_input, part1, part2; generate = generate; alt = ALTERNATIVES);
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use util::check::{self, Invariant};
+use util::{Grid, ParseError};
+
+type Input = Grid<u8>;
+type Output = u32;
+
+fn parse(s: &str) -> Result<Input, ParseError> {
+    Grid::try_parse(s, |c| {
+        c.to_digit(10).map(|d| d as u8).ok_or("expected a digit")
+    })
+}
+
+fn low_points(input: &Input) -> impl Iterator<Item = ((usize, usize), &u8)> {
+    input
+        .iter()
+        .filter(|&(pos, &v)| input.quad_neighbors(pos).all(|n| input[n] > v))
+}
+
+const INVARIANTS: &[Invariant<Input>] = &[
+    Invariant::new("the heightmap isn't empty", |input| {
+        check::ensure(input.width() > 0 && input.height() > 0, || {
+            "the heightmap is empty".to_owned()
+        })
+    }),
+    Invariant::new("there are at least three basins", |input| {
+        let n = low_points(input).count();
+        check::ensure(n >= 3, || format!("there are only {} low points", n))
+    }),
+];
+
+fn part1(input: &Input) -> Output {
+    low_points(input).map(|(_, &v)| v as u32 + 1).sum()
+}
+
+fn part2(input: &Input) -> Output {
+    let mut basins = HashMap::<(usize, usize), Vec<(usize, usize)>>::new();
+    for (pos, &v) in input.iter() {
+        if v == 9 {
+            continue;
+        }
+
+        let mut low = pos;
+        while let Some(new_low) = input.quad_neighbors(low).find(|&n| input[n] < input[low]) {
+            low = new_low;
+        }
+
+        basins.entry(low).or_default().push(pos);
+    }
+
+    let mut sizes = basins.values().map(Vec::len).collect::<Vec<_>>();
+    sizes.sort();
+    sizes.iter().rev().map(|&x| x as u32).take(3).product()
+}
+
+util::register_alt!(try parse, part1, part2; check = INVARIANTS);
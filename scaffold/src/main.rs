@@ -0,0 +1,266 @@
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+
+use util::puzzle::{parse_day, parse_year, DEFAULT_YEAR};
+use util::Puzzle;
+
+const USAGE: &str = "usage: scaffold new [YEAR] DAY [--whole] [--fetch]";
+
+const LINES_TEMPLATE: &str = "type Input = i64;
+type Output = i64;
+
+fn part1(_input: &[Input]) -> Output {
+    todo!()
+}
+
+fn part2(_input: &[Input]) -> Output {
+    todo!()
+}
+
+util::register!(try util::parse::<Input>, part1, part2);
+";
+
+const WHOLE_TEMPLATE: &str = "type Input = Vec<String>;
+type Output = usize;
+
+fn parse(s: &str) -> Input {
+    s.lines().map(str::to_owned).collect()
+}
+
+fn part1(_input: &Input) -> Output {
+    todo!()
+}
+
+fn part2(_input: &Input) -> Output {
+    todo!()
+}
+
+util::register_alt!(parse, part1, part2);
+";
+
+struct New {
+    puzzle: Puzzle,
+    /// `register_alt!`, with the whole input handed to the parser, rather than `register!`.
+    whole: bool,
+    fetch: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<New, String> {
+    let (flags, positional): (Vec<_>, Vec<_>) = args.iter().partition(|a| a.starts_with("--"));
+    let mut new = New {
+        puzzle: match positional[..] {
+            [day] => Puzzle::new(DEFAULT_YEAR, parse_day(day).ok_or("expected a day")?),
+            [year, day] => Puzzle::new(
+                parse_year(year).ok_or("expected a year")?,
+                parse_day(day).ok_or("expected a day")?,
+            ),
+            _ => return Err("expected a day, or a year and a day".to_owned()),
+        },
+        whole: false,
+        fetch: false,
+    };
+    for flag in flags {
+        match flag.as_str() {
+            "--whole" => new.whole = true,
+            "--fetch" => new.fetch = true,
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(new)
+}
+
+/// `dayN` for this repository's own year, which other years' crate names can't clash with.
+fn crate_name(puzzle: Puzzle) -> String {
+    if puzzle.year == DEFAULT_YEAR {
+        format!("day{}", puzzle.day)
+    } else {
+        format!("y{}_day{:02}", puzzle.year, puzzle.day)
+    }
+}
+
+/// Adds `member` to the end of the workspace's `members` list.
+fn add_member(manifest: &str, member: &str) -> Result<String, String> {
+    let start = manifest.find("members = [").ok_or("no workspace members")?;
+    let end = start
+        + manifest[start..]
+            .find("\n]")
+            .ok_or("unclosed members list")?;
+    Ok(format!(
+        "{}\n    \"{}\",{}",
+        &manifest[..end],
+        member,
+        &manifest[end..]
+    ))
+}
+
+/// Adds `dependency` to the end of the runner's `[dependencies]` table.
+fn add_dependency(manifest: &str, dependency: &str) -> Result<String, String> {
+    let start = manifest.find("[dependencies]\n").ok_or("no dependencies")?;
+    let end = manifest[start + 1..]
+        .find("\n[")
+        .map_or(manifest.len(), |i| start + 1 + i);
+    let end = start + manifest[start..end].trim_end().len();
+    Ok(format!(
+        "{}\n{}{}",
+        &manifest[..end],
+        dependency,
+        &manifest[end..]
+    ))
+}
+
+/// Adds `DAY => krate,` to the runner's `puzzles!` block for the puzzle's year, starting a new
+/// block if there isn't one yet.
+fn add_puzzle(source: &str, puzzle: Puzzle, krate: &str) -> Result<String, String> {
+    let start = source
+        .find("puzzles! {\n")
+        .ok_or("no `puzzles!` registry")?;
+    let end = start + source[start..].find("\n}\n").ok_or("unclosed `puzzles!`")?;
+    let entry = format!("        {} => {},", puzzle.day, krate);
+
+    let header = format!("\n    {} => {{\n", puzzle.year);
+    let (at, text) = match source[start..end].find(&header) {
+        Some(i) => {
+            let block = start + i + header.len();
+            let close = block + source[block..end].find("    },").ok_or("unclosed year")?;
+            // Before the first later day, to keep the block in order
+            let mut at = close;
+            let mut offset = block;
+            for line in source[block..close].lines() {
+                let day = line
+                    .trim()
+                    .split(' ')
+                    .next()
+                    .and_then(|d| d.parse::<u8>().ok());
+                if day.is_some_and(|day| day > puzzle.day) {
+                    at = offset;
+                    break;
+                }
+                offset += line.len() + 1;
+            }
+            (at, format!("{}\n", entry))
+        }
+        None => (
+            end + 1,
+            format!("    {} => {{\n{}\n    }},\n", puzzle.year, entry),
+        ),
+    };
+    Ok(format!("{}{}{}", &source[..at], text, &source[at..]))
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> Result<String, String>) -> Result<(), String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    let text = f(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    std::fs::write(path, text).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+fn create(root: &Path, new: &New) -> Result<PathBuf, String> {
+    let puzzle = new.puzzle;
+    let dir = puzzle.dir_in(root);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let krate = crate_name(puzzle);
+    let relative = dir
+        .strip_prefix(root)
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+    let util_path = if puzzle.year == DEFAULT_YEAR {
+        "../util"
+    } else {
+        "../../util"
+    };
+
+    std::fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    let manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nutil = {{ path = \"{}\" }}\n",
+        krate, util_path
+    );
+    write(&dir.join("Cargo.toml"), &manifest)?;
+    let main = format!("fn main() {{\n    util::run({}::solution());\n}}\n", krate);
+    write(&dir.join("src/main.rs"), &main)?;
+    let template = if new.whole {
+        WHOLE_TEMPLATE
+    } else {
+        LINES_TEMPLATE
+    };
+    write(&dir.join("src/lib.rs"), template)?;
+
+    let dependency = format!("{} = {{ path = \"../{}\" }}", krate, relative);
+    edit(&root.join("Cargo.toml"), |s| add_member(s, &relative))?;
+    edit(&root.join("aoc/Cargo.toml"), |s| {
+        add_dependency(s, &dependency)
+    })?;
+    edit(&root.join("aoc/src/lib.rs"), |s| {
+        add_puzzle(s, puzzle, &krate)
+    })?;
+    Ok(dir)
+}
+
+/// The scraper writes the examples and input into the puzzle's directory.
+fn fetch(root: &Path, puzzle: Puzzle) -> Result<(), String> {
+    let status = Command::new(root.join("sc"))
+        .arg(puzzle.to_string())
+        .status()
+        .map_err(|e| format!("couldn't run the scraper (is it built?): {}", e))?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("the scraper failed: {}", status)),
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let new = match args.split_first() {
+        Some((command, rest)) if command == "new" => parse_args(rest),
+        _ => Err("expected a subcommand".to_owned()),
+    };
+    let new = new.unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        exit(2);
+    });
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let result = create(root, &new).and_then(|dir| {
+        println!("created {} in {}", new.puzzle, dir.display());
+        match new.fetch {
+            true => fetch(root, new.puzzle),
+            false => Ok(()),
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn registration() {
+    let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n]\nresolver = \"2\"\n";
+    assert_eq!(
+        add_member(manifest, "day2").unwrap(),
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\nresolver = \"2\"\n"
+    );
+
+    let deps = "[dependencies]\nday1 = { path = \"../day1\" }\n\n[features]\n";
+    assert_eq!(
+        add_dependency(deps, "day2 = { path = \"../day2\" }").unwrap(),
+        "[dependencies]\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n\n[features]\n"
+    );
+
+    let registry = "puzzles! {\n    2021 => {\n        1 => day1,\n        3 => day3,\n    },\n}\n";
+    let added = add_puzzle(registry, Puzzle::new(2021, 2), "day2").unwrap();
+    assert_eq!(
+        added,
+        "puzzles! {\n    2021 => {\n        1 => day1,\n        2 => day2,\n        3 => day3,\n    },\n}\n"
+    );
+    let added = add_puzzle(&added, Puzzle::new(2022, 1), "y2022_day01").unwrap();
+    assert!(added.ends_with("    },\n    2022 => {\n        1 => y2022_day01,\n    },\n}\n"));
+}
@@ -1,9 +1,11 @@
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use reqwest::blocking::Client;
 use soup::prelude::*;
+use util::puzzle::{parse_day, parse_year};
+use util::Puzzle;
 
 fn crate_dir() -> Option<PathBuf> {
     std::env::current_exe()
@@ -50,33 +52,36 @@ fn get_test_output<N: QueryBuilderExt>(part: &N) -> Option<String> {
         .map(|n| n.text())
 }
 
-fn what_year_is_it() -> i32 {
-    use chrono::prelude::*;
-    let tz = FixedOffset::west(5 * 3600);
-    let date = Utc::today().with_timezone(&tz);
-    if date.month() == 12 {
-        date.year()
-    } else {
-        date.year() - 1
-    }
+/// `sc` in a puzzle's directory, or `sc [YEAR] DAY` or `sc YEAR/dayDD` from anywhere.
+fn which_puzzle(args: &[String], repo: &Path) -> Result<(Puzzle, PathBuf)> {
+    let puzzle = match args {
+        [] => {
+            let cwd = std::env::current_dir()?;
+            let puzzle = Puzzle::from_dir(&cwd).context("cwd is not a `dayN` directory")?;
+            return Ok((puzzle, cwd));
+        }
+        // A bare day is the same year here as in the runner and the scaffold
+        [spec] => spec.parse::<Puzzle>().map_err(anyhow::Error::msg)?,
+        [year, day] => Puzzle::new(
+            parse_year(year).context("expected a year")?,
+            parse_day(day).context("expected a day")?,
+        ),
+        _ => bail!("usage: sc [[YEAR] DAY | YEAR/dayDD]"),
+    };
+    Ok((puzzle, puzzle.dir_in(repo)))
 }
 
 fn main() -> Result<()> {
-    let cwd = std::env::current_dir()?;
-
-    let day = cwd
-        .iter()
-        .last()
-        .context("no last segment of cwd")?
-        .to_str()
-        .context("invalid UTF-8")?
-        .strip_prefix("day")
-        .context("cwd does not start with `day`")?
-        .parse::<u8>()?;
-
-    let year = what_year_is_it();
-
-    let cookie = std::fs::read_to_string(crate_dir().context("crate dir fail")?.join("session"))?;
+    let crate_dir = crate_dir().context("crate dir fail")?;
+    let repo = crate_dir
+        .parent()
+        .context("no repository above the scraper")?;
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (puzzle, dir) = which_puzzle(&args, repo)?;
+    let Puzzle { year, day } = puzzle;
+    println!("scraping {} into {}", puzzle, dir.display());
+
+    let cookie = std::fs::read_to_string(crate_dir.join("session"))?;
     let client = Client::new();
 
     let http_get = |url: &str| -> Result<String> {
@@ -109,21 +114,22 @@ fn main() -> Result<()> {
 
     let test_output_1 = get_test_output(part1).context("could not find part 1 test output")?;
 
-    let test_output_2 = if let Some(part2) = parts.get(1) {
-        get_test_output(part2).context("could not find part 2 test output")?
-    } else {
-        "0".to_owned()
-    };
+    let mut test_output = format!("part1: {}\n", test_output_1);
+    println!("expected test output: {}", test_output_1);
 
-    let test_output = format!("{}\n{}", test_output_1, test_output_2);
-
-    println!("expected test output: {} {}", test_output_1, test_output_2);
+    if let Some(part2) = parts.get(1) {
+        let test_output_2 = get_test_output(part2).context("could not find part 2 test output")?;
+        test_output += &format!("part2: {}\n", test_output_2);
+        println!("expected part 2 test output: {}", test_output_2);
+    }
 
     let real_input = http_get(&(base_url + "/input"))?;
 
-    std::fs::write(cwd.join("test.txt"), test_input)?;
-    std::fs::write(cwd.join("test.out.txt"), test_output)?;
-    std::fs::write(cwd.join("input.txt"), real_input)?;
+    let examples_dir = dir.join("examples");
+    std::fs::create_dir_all(&examples_dir)?;
+    std::fs::write(examples_dir.join("test.in"), test_input)?;
+    std::fs::write(examples_dir.join("test.out"), test_output)?;
+    std::fs::write(dir.join("input.txt"), real_input)?;
 
     Ok(())
 }
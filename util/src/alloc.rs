@@ -0,0 +1,186 @@
+//! Allocation counting. It's off unless the `count-alloc` feature is enabled, e.g.
+//! `cargo run --release -p aoc --features count-alloc`, since every allocation pays for a few
+//! atomic operations.
+//!
+//! The counters are process-wide, so measurements only make sense while nothing else is running.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display, Formatter, Write};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, plus bookkeeping.
+pub struct Counting;
+
+impl Counting {
+    fn grow(&self, bytes: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        BYTES.fetch_add(bytes, Relaxed);
+        let live = LIVE.fetch_add(bytes, Relaxed) + bytes;
+        PEAK.fetch_max(live, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    /// Counted as a fresh allocation of the new size, which is what it usually costs.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            self.grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+pub fn enabled() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocs {
+    pub count: usize,
+    pub bytes: usize,
+    /// The most that was live at once, beyond what already was beforehand.
+    pub peak: usize,
+}
+
+impl Display for Allocs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} (peak {})",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// All zeroes unless counting is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocs) {
+    let count = ALLOCATIONS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+
+    let value = f();
+    let allocs = Allocs {
+        count: ALLOCATIONS.load(Relaxed) - count,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: PEAK.load(Relaxed).saturating_sub(live),
+    };
+    (value, allocs)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub parse: Allocs,
+    pub part1: Allocs,
+    pub part2: Allocs,
+}
+
+impl Usage {
+    pub fn phases(&self) -> [(&'static str, Allocs); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+pub fn format_bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{} B", n);
+    }
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// A table of per-day allocations, next to `timing::summary`.
+pub fn summary(days: &[(String, Usage)]) -> String {
+    let mut out = format!(
+        "{:<10} {:<6} {:>10} {:>12} {:>12}\n",
+        "puzzle", "phase", "allocs", "bytes", "peak"
+    );
+    for (name, usage) in days {
+        for (phase, a) in usage.phases() {
+            writeln!(
+                out,
+                "{:<10} {:<6} {:>10} {:>12} {:>12}",
+                name,
+                phase,
+                a.count,
+                format_bytes(a.bytes),
+                format_bytes(a.peak)
+            )
+            .unwrap();
+        }
+    }
+    let peak = days
+        .iter()
+        .flat_map(|(name, usage)| usage.phases().map(|(phase, a)| (a.peak, name, phase)))
+        .max();
+    if let Some((peak, name, phase)) = peak {
+        writeln!(
+            out,
+            "\nhighest peak: {} {} {}",
+            name,
+            phase,
+            format_bytes(peak)
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+#[test]
+fn counting() {
+    assert_eq!(format_bytes(1000), "1000 B");
+    assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+
+    let (v, allocs) = measure(|| vec![0u8; 4096]);
+    if enabled() {
+        assert!(allocs.count >= 1);
+        assert!(allocs.bytes >= v.len() && allocs.peak >= v.len());
+    } else {
+        assert_eq!(allocs, Allocs::default());
+    }
+}
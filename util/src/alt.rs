@@ -0,0 +1,113 @@
+//! Other implementations of a part, like the brute force that a clever solution replaced. They're
+//! cross-checked against the main implementation, by `util::test` and `--cross-check`, and benched
+//! alongside it.
+//!
+//! ```ignore
+//! const ALTERNATIVES: &[Alternative<[i32]>] = &[
+//!     Alternative::new(1, "median", |input| median(input).to_string()),
+//! ];
+//! util::register!(parse, part1, part2; alt = ALTERNATIVES);
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::{normalize_answer, Solution};
+
+pub struct Alternative<In: ?Sized> {
+    pub part: u8,
+    pub name: &'static str,
+    pub run: fn(&In) -> String,
+}
+
+impl<In: ?Sized> Alternative<In> {
+    pub const fn new(part: u8, name: &'static str, run: fn(&In) -> String) -> Self {
+        Self { part, name, run }
+    }
+}
+
+/// How each alternative's answer compared to the main implementation's.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrossCheck {
+    pub results: Vec<(u8, &'static str, Result<(), String>)>,
+}
+
+impl CrossCheck {
+    pub fn run<S: Solution>(solution: &S, input: &S::Input) -> Self {
+        let alternatives = solution.alternatives();
+        let wants = |part| alternatives.iter().any(|&(p, _)| p == part);
+        let part1 = wants(1).then(|| solution.part1(input).to_string());
+        let part2 = (wants(2) && solution.has_part2()).then(|| solution.part2(input).to_string());
+
+        let results = alternatives
+            .iter()
+            .enumerate()
+            .map(|(i, &(part, name))| {
+                let main = match part {
+                    1 => part1.as_deref(),
+                    _ => part2.as_deref(),
+                };
+                let Some(main) = main.map(normalize_answer) else {
+                    return (part, name, Err(format!("there's no part {}", part)));
+                };
+                let answer = normalize_answer(&solution.alternative(i, input));
+                let result = match answer == main {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "got {}, but the main implementation got {}",
+                        answer, main
+                    )),
+                };
+                (part, name, result)
+            })
+            .collect();
+        Self { results }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|(_, _, result)| result.is_ok())
+    }
+}
+
+impl Display for CrossCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.results.is_empty() {
+            return writeln!(f, "no alternatives declared");
+        }
+        for (part, name, result) in &self.results {
+            match result {
+                Ok(()) => writeln!(f, "ok        part{} `{}`", part, name)?,
+                Err(why) => writeln!(f, "DIFFERS   part{} `{}`: {}", part, name, why)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn cross_check() {
+    fn sum(input: &[u32]) -> u32 {
+        input.iter().sum()
+    }
+    const ALTERNATIVES: &[Alternative<[u32]>] = &[
+        Alternative::new(1, "backwards", |input| {
+            input.iter().rev().sum::<u32>().to_string()
+        }),
+        Alternative::new(2, "off by one", |input| (sum(input) * 2 + 1).to_string()),
+    ];
+    let solution = crate::Lines::new(crate::parse_unwrap::<u32>, sum, |input: &[u32]| {
+        sum(input) * 2
+    })
+    .with_alternatives(ALTERNATIVES);
+
+    let input = solution.parse("1\n2\n3").unwrap();
+    let report = CrossCheck::run(&solution, &input);
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.to_string(),
+        concat!(
+            "ok        part1 `backwards`\n",
+            "DIFFERS   part2 `off by one`: got 13, but the main implementation got 12\n",
+        )
+    );
+}
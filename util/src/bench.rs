@@ -0,0 +1,558 @@
+use std::fmt::Write;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, format_bytes, Allocs};
+use crate::{ParseError, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub warm_up: Duration,
+    pub measurement: Duration,
+    pub samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::from_millis(500),
+            measurement: Duration::from_secs(2),
+            samples: 50,
+        }
+    }
+}
+
+impl Config {
+    /// `AOC_BENCH_SECS` overrides the measurement time, e.g. `0.1` for a quick smoke run.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(secs) = std::env::var("AOC_BENCH_SECS")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+        {
+            config.measurement = Duration::from_secs_f64(secs);
+            config.warm_up = config.measurement / 4;
+        }
+        config
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+/// Per-iteration times, in nanoseconds.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub samples: usize,
+    pub iterations: u64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub outliers: Outliers,
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+impl Stats {
+    fn from_samples(mut times: Vec<f64>, iterations: u64) -> Self {
+        times.sort_by(f64::total_cmp);
+
+        let n = times.len() as f64;
+        let mean = times.iter().sum::<f64>() / n;
+        let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+
+        // Tukey's fences
+        let (q1, q3) = (percentile(&times, 0.25), percentile(&times, 0.75));
+        let iqr = q3 - q1;
+        let mut outliers = Outliers::default();
+        for &t in &times {
+            if t < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if t < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if t > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if t > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+
+        Self {
+            samples: times.len(),
+            iterations,
+            median: percentile(&times, 0.5),
+            mean,
+            stddev: variance.sqrt(),
+            min: times[0],
+            max: times[times.len() - 1],
+            outliers,
+        }
+    }
+}
+
+pub fn measure<R>(config: &Config, mut f: impl FnMut() -> R) -> Stats {
+    let warm_up_start = Instant::now();
+    let mut warm_up_runs = 0u32;
+    while warm_up_runs == 0 || warm_up_start.elapsed() < config.warm_up {
+        black_box(f());
+        warm_up_runs += 1;
+    }
+    let estimate = warm_up_start.elapsed() / warm_up_runs;
+
+    // Spread the measurement time over the samples, batching fast functions so that each sample
+    // is long enough for the timer, and taking fewer samples of slow ones.
+    let per_sample = config.measurement / config.samples as u32;
+    let iterations = (per_sample.as_nanos() / estimate.as_nanos().max(1)).max(1) as u64;
+    let samples = if iterations > 1 {
+        config.samples
+    } else {
+        let affordable = config.measurement.as_nanos() / estimate.as_nanos().max(1);
+        (affordable as usize).clamp(3, config.samples)
+    };
+
+    let times = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(f());
+            }
+            start.elapsed().as_nanos() as f64 / iterations as f64
+        })
+        .collect();
+
+    Stats::from_samples(times, iterations)
+}
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub name: String,
+    pub phase: &'static str,
+    /// Which alternative implementation of the part this is, if it isn't the main one.
+    pub alternative: Option<&'static str>,
+    pub stats: Stats,
+    /// From one extra run, with the `count-alloc` feature.
+    pub allocs: Option<Allocs>,
+}
+
+pub fn bench_solution<S: Solution>(
+    name: &str,
+    solution: &S,
+    input_data: &str,
+    config: &Config,
+) -> Result<Vec<Measurement>, ParseError> {
+    let input = solution.parse(input_data)?;
+
+    let parse = (
+        measure(config, || solution.parse(black_box(input_data)).ok()),
+        alloc::measure(|| solution.parse(input_data).ok()).1,
+    );
+    let part1 = (
+        measure(config, || solution.part1(black_box(&input))),
+        alloc::measure(|| solution.part1(&input)).1,
+    );
+    let part2 = solution.has_part2().then(|| {
+        (
+            measure(config, || solution.part2(black_box(&input))),
+            alloc::measure(|| solution.part2(&input)).1,
+        )
+    });
+
+    let alternatives = solution
+        .alternatives()
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, (part, _))| part == 1 || solution.has_part2())
+        .map(|(i, (part, alternative))| {
+            let phase = if part == 1 { "part1" } else { "part2" };
+            let measured = (
+                measure(config, || solution.alternative(i, black_box(&input))),
+                alloc::measure(|| solution.alternative(i, &input)).1,
+            );
+            (phase, Some(alternative), Some(measured))
+        });
+
+    let mut measurements = [
+        ("parse", None, Some(parse)),
+        ("part1", None, Some(part1)),
+        ("part2", None, part2),
+    ]
+    .into_iter()
+    .chain(alternatives)
+    .filter_map(|(phase, alternative, measured)| Some((phase, alternative, measured?)))
+    .map(|(phase, alternative, (stats, allocs))| Measurement {
+        name: name.to_owned(),
+        phase,
+        alternative,
+        stats,
+        allocs: alloc::enabled().then_some(allocs),
+    })
+    .collect::<Vec<_>>();
+    // Each part's alternatives right after it
+    measurements.sort_by_key(|m| m.phase);
+    Ok(measurements)
+}
+
+/// `part1`, or `part1 (brute force)` for an alternative.
+fn phase_label(phase: &str, alternative: Option<&str>) -> String {
+    match alternative {
+        Some(alternative) => format!("{} ({})", phase, alternative),
+        None => phase.to_owned(),
+    }
+}
+
+pub fn format_nanos(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.1} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.2} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2} ms", ns / 1e6)
+    } else {
+        format!("{:.3} s", ns / 1e9)
+    }
+}
+
+pub fn table(results: &[Measurement]) -> String {
+    let with_allocs = results.iter().any(|m| m.allocs.is_some());
+    let alloc_columns = |allocs: Option<Allocs>| match allocs {
+        Some(a) => format!(" {:>10} {:>12}", a.count, format_bytes(a.peak)),
+        None if with_allocs => format!(" {:>10} {:>12}", "-", "-"),
+        None => String::new(),
+    };
+
+    let phases = results
+        .iter()
+        .map(|m| phase_label(m.phase, m.alternative))
+        .collect::<Vec<_>>();
+    let width = phases.iter().map(String::len).max().unwrap_or(0).max(6);
+
+    let mut out = format!(
+        "{:<10} {:<width$} {:>12} {:>12} {:>12} {:>9}{}  outliers\n",
+        "name",
+        "phase",
+        "median",
+        "mean",
+        "stddev",
+        "samples",
+        match with_allocs {
+            true => format!(" {:>10} {:>12}", "allocs", "peak"),
+            false => String::new(),
+        }
+    );
+    for (m, phase) in results.iter().zip(phases) {
+        let s = &m.stats;
+        let o = &s.outliers;
+        let outliers = if o.total() == 0 {
+            String::new()
+        } else {
+            format!(
+                "{} ({} low severe, {} low mild, {} high mild, {} high severe)",
+                o.total(),
+                o.low_severe,
+                o.low_mild,
+                o.high_mild,
+                o.high_severe
+            )
+        };
+        let line = format!(
+            "{:<10} {:<width$} {:>12} {:>12} {:>12} {:>9}{}  {}",
+            m.name,
+            phase,
+            format_nanos(s.median),
+            format_nanos(s.mean),
+            format!("±{}", format_nanos(s.stddev)),
+            format!("{}x{}", s.samples, s.iterations),
+            alloc_columns(m.allocs),
+            outliers
+        );
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn json(results: &[Measurement]) -> String {
+    let entries = results
+        .iter()
+        .map(|m| {
+            let s = &m.stats;
+            let o = &s.outliers;
+            let alternative = match m.alternative {
+                Some(a) => format!(", \"alternative\": {}", json_string(a)),
+                None => String::new(),
+            };
+            let allocs = match m.allocs {
+                Some(a) => format!(
+                    ", \"allocations\": {}, \"bytes\": {}, \"peak_bytes\": {}",
+                    a.count, a.bytes, a.peak
+                ),
+                None => String::new(),
+            };
+            format!(
+                concat!(
+                    "  {{\"name\": {}, \"phase\": {}{}, \"samples\": {}, \"iterations\": {}, ",
+                    "\"median_ns\": {:.1}, \"mean_ns\": {:.1}, \"stddev_ns\": {:.1}, ",
+                    "\"min_ns\": {:.1}, \"max_ns\": {:.1}, \"outliers\": {{\"low_severe\": {}, ",
+                    "\"low_mild\": {}, \"high_mild\": {}, \"high_severe\": {}}}{}}}"
+                ),
+                json_string(&m.name),
+                json_string(m.phase),
+                alternative,
+                s.samples,
+                s.iterations,
+                s.median,
+                s.mean,
+                s.stddev,
+                s.min,
+                s.max,
+                o.low_severe,
+                o.low_mild,
+                o.high_mild,
+                o.high_severe,
+                allocs,
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+/// Prints the table, and writes the JSON to `json_path` if there is one.
+pub fn report(results: &[Measurement], json_path: Option<&Path>) {
+    print!("{}", table(results));
+    if let Some(path) = json_path {
+        std::fs::write(path, json(results))
+            .unwrap_or_else(|e| panic!("couldn't write {}: {}", path.display(), e));
+    }
+}
+
+/// How the median time of a phase grows with the size of a generated input.
+#[derive(Debug, Clone)]
+pub struct Scaling {
+    pub name: String,
+    pub phase: &'static str,
+    pub alternative: Option<&'static str>,
+    /// Sizes and median times, in nanoseconds.
+    pub points: Vec<(usize, f64)>,
+}
+
+impl Scaling {
+    /// The `k` in time ∝ sizeᵏ, from a least-squares fit on a log-log scale.
+    pub fn exponent(&self) -> Option<f64> {
+        let logs = self
+            .points
+            .iter()
+            .map(|&(size, t)| ((size as f64).ln(), t.max(1.0).ln()))
+            .collect::<Vec<_>>();
+        let n = logs.len() as f64;
+        let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx = logs.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+        let sxy = logs
+            .iter()
+            .map(|p| (p.0 - mean_x) * (p.1 - mean_y))
+            .sum::<f64>();
+        (sxx > 0.0).then(|| sxy / sxx)
+    }
+}
+
+/// `100,200,500`, or `100..6400` for every doubling from 100 up to 6400.
+pub fn parse_sizes(s: &str) -> Result<Vec<usize>, String> {
+    let size = |n: &str| {
+        n.parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("`{}` is not a size", n))
+    };
+    let sizes = match s.split_once("..") {
+        Some((lo, hi)) => {
+            let (lo, hi) = (size(lo)?, size(hi)?);
+            std::iter::successors(Some(lo), |n| n.checked_mul(2))
+                .take_while(|&n| n <= hi)
+                .collect()
+        }
+        None => s.split(',').map(size).collect::<Result<Vec<_>, _>>()?,
+    };
+    match sizes.len() {
+        0 | 1 => Err("scaling needs at least two sizes".to_owned()),
+        _ => Ok(sizes),
+    }
+}
+
+/// Benches every phase on made-up inputs of each of `sizes`, or `None` if the day can't make
+/// them up.
+pub fn bench_scaling<S: Solution>(
+    name: &str,
+    solution: &S,
+    sizes: &[usize],
+    seed: u64,
+    config: &Config,
+) -> Option<Result<Vec<Scaling>, ParseError>> {
+    let mut scalings = Vec::<Scaling>::new();
+    for &size in sizes {
+        let input_data = solution.generate(seed, size)?;
+        eprintln!("{}: size {}...", name, size);
+        let measurements = match bench_solution(name, solution, &input_data, config) {
+            Ok(m) => m,
+            Err(e) => {
+                let label = format!("<generated, size {}, seed {}>", size, seed);
+                return Some(Err(e.with_file(label)));
+            }
+        };
+        for m in measurements {
+            let point = (size, m.stats.median);
+            let same = |s: &&mut Scaling| (s.phase, s.alternative) == (m.phase, m.alternative);
+            match scalings.iter_mut().find(same) {
+                Some(scaling) => scaling.points.push(point),
+                None => scalings.push(Scaling {
+                    name: name.to_owned(),
+                    phase: m.phase,
+                    alternative: m.alternative,
+                    points: vec![point],
+                }),
+            }
+        }
+    }
+    Some(Ok(scalings))
+}
+
+/// One row per size and one column per phase, with the fitted exponents underneath.
+pub fn scaling_table(scalings: &[Scaling]) -> String {
+    let labels = scalings
+        .iter()
+        .map(|s| phase_label(s.phase, s.alternative))
+        .collect::<Vec<_>>();
+    let widths = labels.iter().map(|l| l.len().max(12)).collect::<Vec<_>>();
+
+    let mut out = format!("{:<10} {:>10}", "name", "size");
+    for (label, width) in labels.iter().zip(&widths) {
+        write!(out, " {:>width$}", label).unwrap();
+    }
+    out.push('\n');
+
+    let sizes = scalings.first().map_or(&[][..], |s| &s.points[..]);
+    for (i, &(size, _)) in sizes.iter().enumerate() {
+        write!(out, "{:<10} {:>10}", scalings[0].name, size).unwrap();
+        for (s, width) in scalings.iter().zip(&widths) {
+            let time = s
+                .points
+                .get(i)
+                .map_or("-".to_owned(), |p| format_nanos(p.1));
+            write!(out, " {:>width$}", time).unwrap();
+        }
+        out.push('\n');
+    }
+
+    write!(out, "{:<10} {:>10}", "", "exponent").unwrap();
+    for (s, width) in scalings.iter().zip(&widths) {
+        let exponent = s
+            .exponent()
+            .map_or("-".to_owned(), |k| format!("n^{:.2}", k));
+        write!(out, " {:>width$}", exponent).unwrap();
+    }
+    out.push('\n');
+    out
+}
+
+pub fn scaling_json(scalings: &[Scaling]) -> String {
+    let entries = scalings
+        .iter()
+        .map(|s| {
+            let points = s
+                .points
+                .iter()
+                .map(|(size, t)| format!("{{\"size\": {}, \"median_ns\": {:.1}}}", size, t))
+                .collect::<Vec<_>>();
+            let exponent = s
+                .exponent()
+                .map_or("null".to_owned(), |k| format!("{:.3}", k));
+            let alternative = s.alternative.map_or("null".to_owned(), json_string);
+            format!(
+                concat!(
+                    "  {{\"name\": {}, \"phase\": {}, \"alternative\": {}, \"exponent\": {}, ",
+                    "\"points\": [{}]}}"
+                ),
+                json_string(&s.name),
+                json_string(s.phase),
+                alternative,
+                exponent,
+                points.join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+/// Like `report`, for `bench_scaling`.
+pub fn report_scaling(scalings: &[Scaling], json_path: Option<&Path>) {
+    print!("{}", scaling_table(scalings));
+    if let Some(path) = json_path {
+        std::fs::write(path, scaling_json(scalings))
+            .unwrap_or_else(|e| panic!("couldn't write {}: {}", path.display(), e));
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn exponents() {
+    let scaling = |f: fn(f64) -> f64| Scaling {
+        name: "day".to_owned(),
+        phase: "part1",
+        alternative: None,
+        points: [10, 20, 40, 80].map(|n| (n, f(n as f64))).to_vec(),
+    };
+    let k = scaling(|n| 3.0 * n * n).exponent().unwrap();
+    assert!((k - 2.0).abs() < 1e-9);
+    let k = scaling(|n| 50.0 * n * n.ln()).exponent().unwrap();
+    assert!(k > 1.0 && k < 1.5);
+    assert!(scaling(|_| 5.0).exponent().unwrap().abs() < 1e-9);
+    let table = scaling_table(&[scaling(|n| n * 1e3)]);
+    assert!(table.contains("day                80     80.00 µs\n"));
+    assert!(table.ends_with("exponent       n^1.00\n"));
+
+    assert_eq!(parse_sizes("100..800"), Ok(vec![100, 200, 400, 800]));
+    assert_eq!(parse_sizes("5,7"), Ok(vec![5, 7]));
+    assert!(parse_sizes("5").is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn outliers() {
+    let mut times = vec![10.0; 20];
+    times.extend([11.0, 9.0, 30.0, 1000.0]);
+    let stats = Stats::from_samples(times, 1);
+    assert_eq!(stats.median, 10.0);
+    assert_eq!(stats.min, 9.0);
+    assert_eq!(stats.outliers.high_severe, 3);
+    assert_eq!(stats.outliers.low_severe, 1);
+}
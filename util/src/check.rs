@@ -0,0 +1,120 @@
+//! Invariants that a day's solution assumes about its parsed input, which `--check` validates.
+//!
+//! ```ignore
+//! const INVARIANTS: &[Invariant<[Vec<bool>]>] = &[
+//!     Invariant::new("numbers are all the same width", |lines| check::same_len(lines, "line")),
+//! ];
+//! util::register!(try parse, part1, part2; check = INVARIANTS);
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+pub struct Invariant<In: ?Sized> {
+    pub name: &'static str,
+    /// Explains what's wrong if the invariant doesn't hold.
+    pub check: fn(&In) -> Result<(), String>,
+}
+
+impl<In: ?Sized> Invariant<In> {
+    pub const fn new(name: &'static str, check: fn(&In) -> Result<(), String>) -> Self {
+        Self { name, check }
+    }
+}
+
+/// How an input fared against each invariant, in the order they were declared.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub results: Vec<(&'static str, Result<(), String>)>,
+}
+
+impl Report {
+    pub fn run<In: ?Sized>(invariants: &[Invariant<In>], input: &In) -> Self {
+        let results = invariants
+            .iter()
+            .map(|invariant| (invariant.name, (invariant.check)(input)))
+            .collect();
+        Self { results }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|(_, result)| result.is_ok())
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.results.is_empty() {
+            return writeln!(f, "no invariants declared");
+        }
+        for (name, result) in &self.results {
+            match result {
+                Ok(()) => writeln!(f, "ok        {}", name)?,
+                Err(why) => writeln!(f, "VIOLATED  {}: {}", name, why)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn ensure(holds: bool, why: impl FnOnce() -> String) -> Result<(), String> {
+    match holds {
+        true => Ok(()),
+        false => Err(why()),
+    }
+}
+
+/// That there are exactly `expected` of `what`, e.g. `count(n, 14, "`inp` instructions")`.
+pub fn count(actual: usize, expected: usize, what: &str) -> Result<(), String> {
+    ensure(actual == expected, || {
+        format!("expected {} {}, found {}", expected, what, actual)
+    })
+}
+
+pub fn non_empty<T>(items: &[T], what: &str) -> Result<(), String> {
+    ensure(!items.is_empty(), || format!("there are no {}s", what))
+}
+
+/// That every item is as long as the first, naming the first one that isn't (counting from 1).
+pub fn same_len<T: AsRef<[U]>, U>(items: &[T], what: &str) -> Result<(), String> {
+    let Some(first) = items.first().map(|item| item.as_ref().len()) else {
+        return Ok(());
+    };
+    for (i, item) in items.iter().enumerate() {
+        let len = item.as_ref().len();
+        ensure(len == first, || {
+            format!(
+                "{} {} has {} items, but {} 1 has {}",
+                what,
+                i + 1,
+                len,
+                what,
+                first
+            )
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn invariants() {
+    let invariants: &[Invariant<[Vec<u8>]>] = &[
+        Invariant::new("there are rows", |rows| non_empty(rows, "row")),
+        Invariant::new("rows line up", |rows| same_len(rows, "row")),
+        Invariant::new("there are two rows", |rows| count(rows.len(), 2, "rows")),
+    ];
+
+    let report = Report::run(invariants, &[vec![1, 2], vec![3, 4]][..]);
+    assert!(report.is_ok());
+
+    let report = Report::run(invariants, &[vec![1, 2], vec![3], vec![4, 5]][..]);
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.to_string(),
+        concat!(
+            "ok        there are rows\n",
+            "VIOLATED  rows line up: row 2 has 1 items, but row 1 has 2\n",
+            "VIOLATED  there are two rows: expected 2 rows, found 3\n",
+        )
+    );
+}
@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use crate::viz::{Options, Target};
+use crate::InputSource;
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub input: InputSource,
+    pub bench: bool,
+    pub json: Option<PathBuf>,
+    pub verify: bool,
+    pub record: bool,
+    pub parallel: bool,
+    pub visualize: Option<Options>,
+    /// Check the input against the solution's invariants instead of solving it.
+    pub check: bool,
+    /// Compare the answers of every implementation of each part instead of just solving it.
+    pub cross_check: bool,
+    /// Feed the solution the input a line at a time, for days that can take it that way.
+    pub stream: bool,
+    /// Shrink the input for as long as it fails this way, and save it as an example.
+    pub reduce: Option<crate::reduce::Failure>,
+    /// Print a made-up input of this size instead of solving anything.
+    pub generate: Option<usize>,
+    /// Bench made-up inputs of these sizes, to see how the time grows.
+    pub scale: Option<Vec<usize>>,
+    pub seed: u64,
+}
+
+impl Args {
+    /// The flags that every binary takes, for usage messages.
+    pub const FLAGS: &'static str = concat!(
+        "[--parallel] [--verify] [--record] [--bench [--json PATH]]\n",
+        "    [--check] [--cross-check] [--stream] [--generate SIZE | --scale SIZES] [--seed N]\n",
+        "    [--reduce panic|mismatch|timeout[=SECS]]\n",
+        "    [--visualize[=DIR] [--format ppm|png|gif] [--fps N]]"
+    );
+
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        Self::parse_with(args, |_, _| Ok(false))
+    }
+
+    /// Like `parse`, but `extra` gets the first look at each argument, and the ones after it, and
+    /// says whether it took it. The multi-day runner takes its own flags and the days this way.
+    pub fn parse_with<I, F>(args: I, mut extra: F) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+        F: FnMut(&str, &mut dyn Iterator<Item = String>) -> Result<bool, String>,
+    {
+        let mut this = Self::default();
+        let mut args = args.into_iter();
+        let mut viz_dir = None;
+        let mut format = None;
+        let mut fps = None;
+        let mut seed = None;
+        while let Some(arg) = args.next() {
+            if extra(&arg, &mut args)? {
+                continue;
+            } else if arg == "--bench" {
+                this.bench = true;
+                continue;
+            } else if arg == "--verify" {
+                this.verify = true;
+                continue;
+            } else if arg == "--record" {
+                this.record = true;
+                continue;
+            } else if arg == "--check" {
+                this.check = true;
+                continue;
+            } else if arg == "--cross-check" {
+                this.cross_check = true;
+                continue;
+            } else if arg == "--reduce" {
+                let failure = args
+                    .next()
+                    .ok_or("`--reduce` needs panic, mismatch or timeout")?;
+                this.reduce = Some(crate::reduce::Failure::parse(&failure)?);
+                continue;
+            } else if arg == "--stream" {
+                this.stream = true;
+                continue;
+            } else if arg == "--generate" {
+                let n = args.next().and_then(|n| n.parse().ok());
+                this.generate = Some(n.ok_or("`--generate` needs a size")?);
+                continue;
+            } else if arg == "--scale" {
+                let sizes = args
+                    .next()
+                    .ok_or("`--scale` needs sizes, like `100..6400`")?;
+                this.scale = Some(crate::bench::parse_sizes(&sizes)?);
+                continue;
+            } else if arg == "--seed" {
+                let n = args.next().and_then(|n| n.parse().ok());
+                seed = Some(n.ok_or("`--seed` needs a number")?);
+                continue;
+            } else if arg == "--parallel" {
+                this.parallel = true;
+                continue;
+            } else if arg == "--json" {
+                this.json = Some(args.next().ok_or("`--json` needs a path")?.into());
+                continue;
+            } else if arg == "--visualize" {
+                this.visualize = Some(Options::default());
+                continue;
+            } else if let Some(dir) = arg.strip_prefix("--visualize=") {
+                this.visualize = Some(Options::default());
+                viz_dir = Some(PathBuf::from(dir));
+                continue;
+            } else if arg == "--format" {
+                format = Some(args.next().ok_or("`--format` needs ppm, png or gif")?);
+                continue;
+            } else if arg == "--fps" {
+                let n = args.next().and_then(|n| n.parse().ok());
+                fps = Some(n.ok_or("`--fps` needs a number")?);
+                continue;
+            }
+
+            let source = if arg == "-" {
+                InputSource::Stdin
+            } else if arg == "--input" {
+                InputSource::Named(args.next().ok_or("`--input` needs a name")?)
+            } else if let Some(name) = arg.strip_prefix("--input=") {
+                InputSource::Named(name.to_owned())
+            } else if arg.starts_with('-') {
+                return Err(format!("unknown flag `{}`", arg));
+            } else {
+                InputSource::Path(arg.into())
+            };
+
+            if this.input != InputSource::Default {
+                return Err("only one input can be given".to_owned());
+            }
+            this.input = source;
+        }
+
+        if let Some(options) = &mut this.visualize {
+            options.target = match (viz_dir, format.as_deref()) {
+                (None, None) => Target::Terminal,
+                (None, Some(_)) => return Err("`--format` needs `--visualize=DIR`".to_owned()),
+                (Some(dir), None | Some("png")) => Target::Png(dir),
+                (Some(dir), Some("ppm")) => Target::Ppm(dir),
+                (Some(dir), Some("gif")) => Target::Gif(dir),
+                (Some(_), Some(f)) => return Err(format!("unknown format `{}`", f)),
+            };
+            options.fps = fps.unwrap_or(options.fps);
+        } else if format.is_some() || fps.is_some() {
+            return Err("`--format` and `--fps` need `--visualize`".to_owned());
+        }
+        if seed.is_some() && this.generate.is_none() && this.scale.is_none() {
+            return Err("`--seed` needs `--generate` or `--scale`".to_owned());
+        }
+        if this.generate.is_some()
+            && matches!(this.input, InputSource::Path(_) | InputSource::Stdin)
+        {
+            return Err("`--generate` prints the input, or writes it to `--input NAME`".to_owned());
+        }
+        this.seed = seed.unwrap_or_default();
+        Ok(this)
+    }
+
+    pub fn from_env() -> Self {
+        Self::parse(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{}\nusage: [PATH | - | --input NAME] {}", e, Self::FLAGS);
+            std::process::exit(2);
+        })
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn flags() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    let parsed = Args::parse(args(&["--bench", "-"])).unwrap();
+    assert!(parsed.bench && parsed.input == InputSource::Stdin);
+    assert!(Args::parse(args(&["--generate", "5", "input.txt"])).is_err());
+
+    let mut days = vec![];
+    let parsed = Args::parse_with(args(&["7", "--stream", "test.in"]), |arg, _| {
+        let day = arg.parse::<u8>();
+        days.extend(day.clone());
+        Ok(day.is_ok())
+    })
+    .unwrap();
+    assert_eq!(days, [7]);
+    assert!(parsed.stream && parsed.input == InputSource::Path("test.in".into()));
+}
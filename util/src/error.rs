@@ -0,0 +1,166 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub span: Range<usize>,
+    pub text: String,
+}
+
+fn offset_within(outer: &str, inner: &str) -> Option<usize> {
+    let offset = (inner.as_ptr() as usize).checked_sub(outer.as_ptr() as usize)?;
+    (offset + inner.len() <= outer.len()).then_some(offset)
+}
+
+impl ParseError {
+    pub fn new(text: &str, span: Range<usize>, message: impl Display) -> Self {
+        Self {
+            message: message.to_string(),
+            file: None,
+            line: None,
+            span,
+            text: text.to_owned(),
+        }
+    }
+
+    pub fn whole(text: &str, message: impl Display) -> Self {
+        Self::new(text, 0..text.len(), message)
+    }
+
+    /// `part` must be a subslice of `text`, e.g. one half of a `split_once`.
+    pub fn at(text: &str, part: &str, message: impl Display) -> Self {
+        match offset_within(text, part) {
+            Some(start) => Self::new(text, start..start + part.len(), message),
+            None => Self::whole(text, message),
+        }
+    }
+
+    /// Re-anchors an error produced while parsing `inner` so that it points into `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        if self.text.is_empty() {
+            self.span = 0..inner.len();
+        }
+        if let Some(offset) = offset_within(outer, inner) {
+            self.span = self.span.start + offset..self.span.end + offset;
+            self.text = outer.to_owned();
+        }
+        self
+    }
+
+    /// For an input that couldn't be read at all, so there's no text to point into.
+    pub fn unreadable(file: impl AsRef<Path>, e: std::io::Error) -> Self {
+        Self::new("", 0..0, format!("couldn't read the input: {}", e)).with_file(file)
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_owned());
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+
+        if self.text.is_empty() {
+            if let Some(file) = &self.file {
+                return write!(f, "\n--> {}", file.display());
+            }
+        }
+
+        // Only the line the span starts on is shown, and the caret can't run past its end
+        let before = self.text.get(..self.span.start).unwrap_or(&self.text);
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let text = self.text[start..].lines().next().unwrap_or_default();
+        let col = before[start..].chars().count();
+        let width = self
+            .text
+            .get(self.span.start..self.span.end.min(start + text.len()))
+            .map_or(0, |s| s.chars().count());
+        let line = self
+            .line
+            .unwrap_or_else(|| 1 + before.matches('\n').count());
+        let line_no = line.to_string();
+        let gutter = " ".repeat(line_no.len());
+
+        match &self.file {
+            Some(file) => write!(f, "\n{}--> {}:{}:{}", gutter, file.display(), line, col + 1)?,
+            None => write!(f, "\n{}--> line {}:{}", gutter, line, col + 1)?,
+        }
+        write!(f, "\n{} |", gutter)?;
+        write!(f, "\n{} | {}", line_no, text)?;
+        write!(
+            f,
+            "\n{} | {}{}",
+            gutter,
+            " ".repeat(col),
+            "^".repeat(width.max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+macro_rules! from_std_errors {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for ParseError {
+                fn from(e: $ty) -> Self {
+                    Self::new("", 0..0, e)
+                }
+            }
+        )*
+    };
+}
+
+from_std_errors!(
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::str::ParseBoolError,
+    std::char::ParseCharError
+);
+
+impl From<std::convert::Infallible> for ParseError {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn diagnostic() {
+    let line = "  sideways 8";
+    let trimmed = line.trim();
+    let (direction, _) = trimmed.split_once(' ').unwrap();
+
+    let e = ParseError::at(trimmed, direction, "unknown direction")
+        .within(line, trimmed)
+        .with_line(3)
+        .with_file("input.txt");
+
+    assert_eq!(e.span, 2..10);
+    assert_eq!(
+        e.to_string(),
+        "error: unknown direction\n --> input.txt:3:3\n  |\n3 |   sideways 8\n  |   ^^^^^^^^"
+    );
+
+    let e = crate::try_parse_input("no/such/input.txt", crate::parse::<u8>).unwrap_err();
+    assert!(e.message.starts_with("couldn't read the input: "));
+    assert!(e.to_string().ends_with("\n--> no/such/input.txt"));
+
+    let text = "forward 5\nup\nsideways 8\n";
+    let e = ParseError::at(text, &text[13..21], "unknown direction");
+    assert_eq!(
+        e.to_string(),
+        "error: unknown direction\n --> line 3:1\n  |\n3 | sideways 8\n  | ^^^^^^^^"
+    );
+}
@@ -0,0 +1,225 @@
+use std::path::{Path, PathBuf};
+
+use crate::alt::CrossCheck;
+use crate::{normalize_answer, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: String,
+    pub path: PathBuf,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Reads `part1: ANSWER` / `part2: ANSWER` lines. A bare `partN:` starts a multi-line answer
+/// that runs until the next `partN:`. Files without those prefixes are read the old way, with part
+/// 1's answer on the first line and part 2's on the second.
+pub fn parse_expected(data: &str) -> (Option<String>, Option<String>) {
+    let mut parts = [None::<String>, None];
+    let mut multi_line = None;
+    let mut unprefixed = vec![];
+    for line in data.lines() {
+        let trimmed = line.trim();
+        let header = [("part1:", 0), ("part2:", 1)]
+            .into_iter()
+            .find_map(|(prefix, i)| Some((trimmed.strip_prefix(prefix)?.trim(), i)));
+
+        if let Some((answer, i)) = header {
+            parts[i] = Some(answer.to_owned());
+            multi_line = answer.is_empty().then_some(i);
+        } else if let Some(i) = multi_line {
+            let answer = parts[i].as_mut().unwrap();
+            answer.push_str(line);
+            answer.push('\n');
+        } else if !trimmed.is_empty() {
+            unprefixed.push(trimmed.to_owned());
+        }
+    }
+
+    let [part1, part2] = parts.map(|part| part.map(|answer| normalize_answer(&answer)));
+    if part1.is_none() && part2.is_none() {
+        let mut unprefixed = unprefixed.into_iter();
+        return (unprefixed.next(), unprefixed.next());
+    }
+    (part1, part2)
+}
+
+fn read_example(name: String, input_path: PathBuf, output_path: PathBuf) -> Example {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("couldn't read {}: {}", path.display(), e))
+    };
+    let input = read(&input_path);
+    let (part1, part2) = parse_expected(&read(&output_path));
+    Example {
+        name,
+        path: input_path,
+        input,
+        part1,
+        part2,
+    }
+}
+
+/// Collects `examples/NAME.in` + `examples/NAME.out` pairs from a day's directory, along with the
+/// scraper's older `test.txt` + `test.out.txt` if present.
+pub fn load_examples(dir: &Path) -> Vec<Example> {
+    let mut examples = vec![];
+
+    if let Ok(entries) = std::fs::read_dir(dir.join("examples")) {
+        for entry in entries {
+            let input_path = entry.unwrap().path();
+            if input_path.extension().is_none_or(|ext| ext != "in") {
+                continue;
+            }
+            let name = input_path
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned();
+            let output_path = input_path.with_extension("out");
+            examples.push(read_example(name, input_path, output_path));
+        }
+    }
+
+    let (legacy_in, legacy_out) = (dir.join("test.txt"), dir.join("test.out.txt"));
+    if legacy_in.exists() && legacy_out.exists() {
+        examples.push(read_example("test".to_owned(), legacy_in, legacy_out));
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    examples
+}
+
+/// Puts multi-line answers on lines of their own in messages.
+pub(crate) fn multi_line(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}\n", answer)
+    } else {
+        answer.to_owned()
+    }
+}
+
+pub fn check_example<S: Solution>(solution: &S, example: &Example, part: u8) -> Result<(), String> {
+    let expected = match example.expected(part) {
+        Some(answer) => answer,
+        None => return Ok(()),
+    };
+
+    let input = solution.parse(&example.input).map_err(|e| {
+        format!(
+            "example `{}`:\n{}",
+            example.name,
+            e.with_file(&example.path)
+        )
+    })?;
+
+    let actual = match part {
+        1 => solution.part1(&input).to_string(),
+        _ if solution.has_part2() => solution.part2(&input).to_string(),
+        _ => {
+            return Err(format!(
+                "example `{}` expects a part 2, but there isn't one",
+                example.name
+            ))
+        }
+    };
+
+    let actual = normalize_answer(&actual);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "example `{}` part {}: expected {}, got {}",
+            example.name,
+            part,
+            multi_line(expected),
+            multi_line(&actual)
+        ))
+    }
+}
+
+pub fn test<S: Solution>(solution: S) {
+    let examples = load_examples(Path::new("."));
+    // A day without them would pass without checking anything
+    assert!(
+        !examples.is_empty(),
+        "no examples found, which go in examples/NAME.in and examples/NAME.out"
+    );
+
+    let mut failures = examples
+        .iter()
+        .flat_map(|example| [1, 2].map(|part| check_example(&solution, example, part)))
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+
+    // Alternative implementations have to agree with the main ones
+    for example in &examples {
+        let Ok(input) = solution.parse(&example.input) else {
+            continue;
+        };
+        let report = CrossCheck::run(&solution, &input);
+        if !report.is_ok() {
+            failures.push(format!("example `{}`:\n{}", example.name, report));
+        }
+    }
+
+    // Made-up inputs have to parse, hold up to `--check`, and get the same answers from every
+    // implementation, for days that can make them
+    for seed in 0..3 {
+        let Some(data) = solution.generate(seed, 10) else {
+            break;
+        };
+        let input = match solution.parse(&data) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("generated input (seed {}):\n{}", seed, e));
+                continue;
+            }
+        };
+        let report = solution.check(&input);
+        if !report.is_ok() {
+            failures.push(format!("generated input (seed {}):\n{}", seed, report));
+        }
+        let report = CrossCheck::run(&solution, &input);
+        if !report.is_ok() {
+            failures.push(format!("generated input (seed {}):\n{}", seed, report));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n"));
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn expected_answers() {
+    let both = "part1: 16\npart2: 3\n";
+    assert_eq!(parse_expected(both), (Some("16".into()), Some("3".into())));
+
+    let only_part2 = "part2: 54\n";
+    assert_eq!(parse_expected(only_part2), (None, Some("54".into())));
+
+    let art = "part1: 17\npart2:\n#####\n#...#  \n#####\n\n";
+    assert_eq!(
+        parse_expected(art),
+        (Some("17".into()), Some("#####\n#...#\n#####".into()))
+    );
+
+    let legacy = "5934\n26984457539";
+    assert_eq!(
+        parse_expected(legacy),
+        (Some("5934".into()), Some("26984457539".into()))
+    );
+}
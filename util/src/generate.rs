@@ -0,0 +1,76 @@
+//! Synthetic puzzle inputs of any size, for scale testing and fuzzing. A day registers a
+//! generator with `; generate = generate`, and the same seed and size always give the same input.
+
+use std::ops::RangeInclusive;
+
+/// Takes a size, in whatever unit makes sense for the day, e.g. lines or scanners.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// SplitMix64, which is plenty for making up inputs and doesn't change between versions of a
+/// crate like `rand` might.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// From 0 up to, but not including, `n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "nothing is below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        let span = hi.abs_diff(lo);
+        match span.checked_add(1) {
+            Some(n) => lo.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn seeded() {
+    let draw = |seed| {
+        let mut rng = Rng::new(seed);
+        [0; 8].map(|_| rng.range(-3..=3))
+    };
+    assert_eq!(draw(7), draw(7));
+    assert_ne!(draw(7), draw(8));
+    assert!(draw(7).iter().all(|n| (-3..=3).contains(n)));
+
+    let mut rng = Rng::new(1);
+    assert_eq!(rng.range(5..=5), 5);
+    assert!((0..1000).all(|_| rng.below(3) < 3));
+    let mut items = [1, 2, 3, 4, 5];
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, [1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,274 @@
+//! Points and vectors in any number of dimensions, and the 24 rotations of 3D space that line the
+//! axes up with each other.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+/// A point, or the vector between two. Points are ordered by their first coordinate, then their
+/// second, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize, T = i64>(pub [T; N]);
+
+pub type P2<T = i64> = Point<2, T>;
+pub type P3<T = i64> = Point<3, T>;
+
+impl<const N: usize, T> Point<N, T> {
+    pub const fn new(coords: [T; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<T: Copy> Point<2, T> {
+    pub fn x(self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> Point<3, T> {
+    pub fn x(self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(self) -> T {
+        self.0[2]
+    }
+}
+
+/// How far apart `a` and `b` are, which works for unsigned coordinates too.
+fn distance<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+impl<const N: usize, T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>>
+    Point<N, T>
+{
+    /// The sum of the distances along each axis.
+    pub fn manhattan(self, other: Self) -> T {
+        (0..N).fold(T::default(), |sum, i| sum + distance(self.0[i], other.0[i]))
+    }
+
+    /// The longest of the distances along each axis, i.e. the number of king's moves apart.
+    pub fn chebyshev(self, other: Self) -> T {
+        (0..N)
+            .map(|i| distance(self.0[i], other.0[i]))
+            .fold(T::default(), |max, d| if d > max { d } else { max })
+    }
+}
+
+impl<const N: usize, T: Copy + Default> Default for Point<N, T> {
+    fn default() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+impl<const N: usize, T> From<[T; N]> for Point<N, T> {
+    fn from(coords: [T; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<T> From<(T, T)> for Point<2, T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self([x, y])
+    }
+}
+
+impl<T> From<(T, T, T)> for Point<3, T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self([x, y, z])
+    }
+}
+
+impl<const N: usize, T> Index<usize> for Point<N, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.0[i]
+    }
+}
+
+impl<const N: usize, T> IndexMut<usize> for Point<N, T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0[i]
+    }
+}
+
+impl<const N: usize, T: Copy + Add<Output = T>> Add for Point<N, T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize, T: Copy + Sub<Output = T>> Sub for Point<N, T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const N: usize, T: Copy + Neg<Output = T>> Neg for Point<N, T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(Neg::neg))
+    }
+}
+
+/// Scaling by a factor.
+impl<const N: usize, T: Copy + Mul<Output = T>> Mul<T> for Point<N, T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<const N: usize, T: Copy + Add<Output = T>> AddAssign for Point<N, T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize, T: Copy + Sub<Output = T>> SubAssign for Point<N, T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// `x,y,z`, the way puzzle inputs write them.
+impl<const N: usize, T: Display> Display for Point<N, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// A rotation that lines the axes up with each other, as the matrix that applies it. Matrices
+/// multiply the usual way, so `a * b` is `b` and then `a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation(pub [[i8; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Self = Self([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+    /// A quarter turn about the x axis, counterclockwise when looking back along it, like the
+    /// right-hand rule has it. `Y` and `Z` are the same about their axes.
+    pub const X: Self = Self([[1, 0, 0], [0, 0, -1], [0, 1, 0]]);
+    pub const Y: Self = Self([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+    pub const Z: Self = Self([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+
+    /// Every way of facing, starting with the identity: each axis can end up along any of the
+    /// six directions, and then the rest can turn four ways about it.
+    pub fn all() -> [Self; 24] {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut all = vec![];
+        for axes in PERMUTATIONS {
+            for signs in 0..8 {
+                let mut m = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    m[row][axis] = if signs >> row & 1 == 0 { 1 } else { -1 };
+                }
+                // The rest are reflections
+                if Self(m).determinant() == 1 {
+                    all.push(Self(m));
+                }
+            }
+        }
+        all.try_into().unwrap()
+    }
+
+    fn determinant(self) -> i8 {
+        let m = self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// The rotation that undoes this one, which for these is just the transpose.
+    pub fn inverse(self) -> Self {
+        Self(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.0[j][i])
+        }))
+    }
+}
+
+impl Mul for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let entry = |i: usize, j: usize| (0..3).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+        Self(std::array::from_fn(|i| {
+            std::array::from_fn(|j| entry(i, j))
+        }))
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T> + Neg<Output = T>> Mul<P3<T>> for Rotation {
+    type Output = P3<T>;
+
+    fn mul(self, p: P3<T>) -> P3<T> {
+        Point(self.0.map(|row| {
+            (0..3).fold(T::default(), |sum, j| match row[j] {
+                1 => sum + p[j],
+                -1 => sum + -p[j],
+                _ => sum,
+            })
+        }))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn points_and_rotations() {
+    let (a, b) = (P3::new([1, -2, 3]), P3::new([4, 2, -1]));
+    assert_eq!(a + b, P3::new([5, 0, 2]));
+    assert_eq!(a - b, -(b - a));
+    assert_eq!(a * 2, a + a);
+    assert_eq!((a.manhattan(b), a.chebyshev(b)), (11, 4));
+    assert_eq!(P2::<usize>::from((3, 8)).manhattan(P2::new([5, 1])), 9);
+    assert!(P2::new([1, 9]) < P2::new([2, 0]));
+    assert_eq!(a.to_string(), "1,-2,3");
+
+    let all = Rotation::all();
+    assert_eq!(all[0], Rotation::IDENTITY);
+    for (i, &r) in all.iter().enumerate() {
+        assert!(!all[..i].contains(&r));
+        assert_eq!(r * r.inverse(), Rotation::IDENTITY);
+        assert!(all.iter().all(|&s| all.contains(&(r * s))));
+    }
+    for r in [Rotation::X, Rotation::Y, Rotation::Z] {
+        assert_eq!(r * r * r * r, Rotation::IDENTITY);
+    }
+    let p = P3::new([1, 2, 3]);
+    assert_eq!(Rotation::X * p, P3::new([1, -3, 2]));
+    assert_eq!(
+        Rotation::Z * (Rotation::X * p),
+        (Rotation::Z * Rotation::X) * p
+    );
+}
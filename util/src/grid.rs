@@ -0,0 +1,284 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut, Range};
+
+use crate::ParseError;
+
+/// `(x, y)`, with `y` growing downwards.
+pub type Point = (usize, usize);
+
+const QUAD: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const OCT: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+/// A dense grid, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "ragged grid");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from a character map, one line per row. Surrounding whitespace is ignored.
+    pub fn parse(s: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(s, |c| Ok::<_, std::convert::Infallible>(cell(c))).unwrap()
+    }
+
+    pub fn try_parse<E: Display>(
+        s: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut cells = vec![];
+        // Blank lines around the grid are skipped, but still counted
+        let blank = |(_, line): &(usize, &str)| line.trim().is_empty();
+        let end = s.lines().enumerate().filter(|l| !blank(l)).last();
+        let end = end.map_or(0, |(i, _)| i + 1);
+        let lines = s.lines().enumerate().skip_while(blank);
+        for (i, line) in lines.take_while(|&(i, _)| i < end) {
+            let row = line.trim();
+            let before = cells.len();
+            for (j, c) in row.char_indices() {
+                let value = cell(c).map_err(|e| {
+                    ParseError::at(row, &row[j..j + c.len_utf8()], e)
+                        .within(line, row)
+                        .with_line(i + 1)
+                })?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - before;
+            if *width.get_or_insert(row_width) != row_width {
+                let message = format!("expected {} cells, found {}", width.unwrap(), row_width);
+                return Err(ParseError::at(line, row, message).with_line(i + 1));
+            }
+        }
+        Ok(Self::from_vec(width.unwrap_or(0), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.1 * self.width + p.0])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.1 * self.width + p.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(&mut self.cells)
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// The cells of a rectangle, clipped to the grid, row by row.
+    pub fn region(&self, xs: Range<usize>, ys: Range<usize>) -> impl Iterator<Item = (Point, &T)> {
+        let xs = xs.start.min(self.width)..xs.end.min(self.width);
+        let ys = ys.start.min(self.height)..ys.end.min(self.height);
+        ys.flat_map(move |y| xs.clone().map(move |x| ((x, y), &self[(x, y)])))
+    }
+
+    /// `p` moved by `(dx, dy)`, or `None` if that leaves the grid.
+    pub fn offset(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let p = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(p).then_some(p)
+    }
+
+    /// `p` moved by `(dx, dy)`, wrapping around the edges.
+    pub fn wrapping_offset(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Point {
+        let wrap = |v: usize, d: isize, n: usize| (v as isize + d).rem_euclid(n as isize) as usize;
+        (wrap(x, dx, self.width), wrap(y, dy, self.height))
+    }
+
+    pub fn quad_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        QUAD.into_iter().filter_map(move |d| self.offset(p, d))
+    }
+
+    pub fn oct_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        OCT.into_iter().filter_map(move |d| self.offset(p, d))
+    }
+
+    pub fn wrapping_quad_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        QUAD.into_iter().map(move |d| self.wrapping_offset(p, d))
+    }
+
+    pub fn wrapping_oct_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        OCT.into_iter().map(move |d| self.wrapping_offset(p, d))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        Self::from_fn(h, self.width, |(x, y)| self[(y, h - 1 - x)].clone())
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width;
+        Self::from_fn(self.height, w, |(x, y)| self[(w - 1 - y, x)].clone())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", p))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn grid() {
+    let g = Grid::parse("123\n456\n", |c| c.to_digit(10).unwrap());
+    assert_eq!((g.width(), g.height()), (3, 2));
+    assert_eq!(g[(2, 1)], 6);
+    assert_eq!(g.get((3, 0)), None);
+
+    assert_eq!(
+        g.quad_neighbors((0, 0)).collect::<Vec<_>>(),
+        [(1, 0), (0, 1)]
+    );
+    assert_eq!(g.oct_neighbors((1, 0)).count(), 5);
+    assert_eq!(g.wrapping_quad_neighbors((0, 0)).next(), Some((2, 0)));
+
+    assert_eq!(g.col(1).copied().collect::<Vec<_>>(), [2, 5]);
+    assert_eq!(g.transpose().to_string(), "14\n25\n36\n");
+    assert_eq!(g.rotate_cw().to_string(), "41\n52\n63\n");
+    assert_eq!(g.rotate_ccw().to_string(), "36\n25\n14\n");
+
+    let region = g.region(1..5, 0..1).map(|(_, &v)| v).collect::<Vec<_>>();
+    assert_eq!(region, [2, 3]);
+
+    let e = Grid::try_parse("12\n3x", |c| c.to_digit(10).ok_or("not a digit")).unwrap_err();
+    assert_eq!((e.line, e.span), (Some(2), 1..2));
+    let e = Grid::try_parse("\n\n 12\n 3x\n\n", |c| c.to_digit(10).ok_or("not a digit"));
+    assert_eq!(e.map_err(|e| (e.line, e.span)), Err((Some(4), 2..3)));
+    assert_eq!(Grid::parse("\n 12\n 34\n\n\n", |c| c).height(), 2);
+}
@@ -0,0 +1,60 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// `input.txt`
+    #[default]
+    Default,
+    /// `input.NAME.txt`, e.g. for another account's input
+    Named(String),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Benches can't take arguments, so they look at `AOC_INPUT=NAME` instead.
+    pub fn from_env() -> Self {
+        match std::env::var("AOC_INPUT") {
+            Ok(name) if !name.is_empty() => Self::Named(name),
+            _ => Self::Default,
+        }
+    }
+
+    pub fn path_in(&self, dir: &Path) -> Option<PathBuf> {
+        match self {
+            Self::Default => Some(dir.join("input.txt")),
+            Self::Named(name) => Some(dir.join(format!("input.{}.txt", name))),
+            Self::Path(path) => Some(dir.join(path)),
+            Self::Stdin => None,
+        }
+    }
+
+    /// What to call this input in diagnostics.
+    pub fn label_in(&self, dir: &Path) -> PathBuf {
+        self.path_in(dir).unwrap_or_else(|| "<stdin>".into())
+    }
+
+    pub fn read_in(&self, dir: &Path) -> std::io::Result<String> {
+        match self.path_in(dir) {
+            Some(path) => std::fs::read_to_string(path),
+            None => {
+                let mut data = String::new();
+                std::io::stdin().read_to_string(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        self.path_in(Path::new(""))
+    }
+
+    pub fn label(&self) -> PathBuf {
+        self.label_in(Path::new(""))
+    }
+
+    pub fn read(&self) -> std::io::Result<String> {
+        self.read_in(Path::new(""))
+    }
+}
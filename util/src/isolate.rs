@@ -0,0 +1,310 @@
+//! Solving a day so that a panic or a part that never finishes is reported, instead of taking the
+//! rest of a multi-day run down with it.
+
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{alloc, phase, timing, Answers, ParseError, Solution, Timings};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<T> {
+    Done(T),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl<T> Outcome<T> {
+    pub fn done(&self) -> Option<&T> {
+        match self {
+            Self::Done(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Display> Display for Outcome<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Done(value) => value.fmt(f),
+            Self::Panicked(message) => write!(f, "panicked: {}", message),
+            Self::TimedOut(limit) => {
+                write!(f, "timed out after {}", timing::format_duration(*limit))
+            }
+        }
+    }
+}
+
+/// How parsing went, and then how each part went if it parsed.
+pub type Report = Outcome<Result<Answers<Outcome<String>>, ParseError>>;
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_owned()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "with something other than a message".to_owned()
+    }
+}
+
+/// What isolated threads are called, so that their panics can be told apart from any others.
+const THREAD: &str = "isolated";
+
+/// Stops panics on isolated threads from printing a message, and a backtrace if they're enabled,
+/// since they're reported along with everything else anyway. Panics anywhere else print as before.
+pub fn quiet_panics() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some(THREAD) {
+            hook(info);
+        }
+    }));
+}
+
+type Phase<T> = (Result<T, String>, Duration, alloc::Allocs);
+
+enum Step {
+    Parsed(Phase<Result<(), ParseError>>),
+    /// Which part, counting from 1, and how it went.
+    Solved(usize, Phase<String>),
+}
+
+fn isolated<T>(f: impl FnOnce() -> T) -> Phase<T> {
+    let ((result, time), allocs) = phase(|| catch_unwind(AssertUnwindSafe(f)));
+    (result.map_err(|e| panic_message(&*e)), time, allocs)
+}
+
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// How many of the threads `solve` started are still going, counting the ones it gave up on.
+pub fn running() -> usize {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+struct Running;
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn thread() -> std::thread::Builder {
+    std::thread::Builder::new().name(THREAD.to_owned())
+}
+
+/// Parses the input and solves `parts` on a thread of its own, reporting each step as it's done.
+/// With `parallel`, the parts run at once on threads of their own, sharing the parsed input.
+fn spawn<S: Solution + Send + 'static>(
+    solution: &Arc<S>,
+    input_data: &Arc<str>,
+    parts: &'static [usize],
+    parallel: bool,
+) -> Receiver<Step> {
+    let (solution, input_data) = (Arc::clone(solution), Arc::clone(input_data));
+    let (tx, rx) = mpsc::channel();
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    let spawned = thread().spawn(move || {
+        let _running = Running;
+        let (input, time, allocs) = isolated(|| solution.parse(&input_data));
+        let (input, parsed) = match input {
+            Ok(Ok(input)) => (Some(input), Ok(Ok(()))),
+            Ok(Err(e)) => (None, Ok(Err(e))),
+            Err(message) => (None, Err(message)),
+        };
+        let _ = tx.send(Step::Parsed((parsed, time, allocs)));
+        let Some(input) = input else {
+            return;
+        };
+        let solve = |part| {
+            isolated(|| match part {
+                1 => solution.part1(&input).to_string(),
+                _ => solution.part2(&input).to_string(),
+            })
+        };
+        if parallel {
+            std::thread::scope(|scope| {
+                for &part in parts {
+                    let (tx, solve) = (tx.clone(), &solve);
+                    let spawned = thread().spawn_scoped(scope, move || {
+                        let _ = tx.send(Step::Solved(part, solve(part)));
+                    });
+                    spawned.expect("couldn't spawn a thread");
+                }
+            });
+            return;
+        }
+        for &part in parts {
+            // Nobody's waiting any more if an earlier part timed out
+            if tx.send(Step::Solved(part, solve(part))).is_err() {
+                return;
+            }
+        }
+    });
+    spawned.expect("couldn't spawn a thread");
+    rx
+}
+
+/// The next step, if it comes within `timeout` of `since`.
+fn next(steps: &Receiver<Step>, timeout: Option<Duration>, since: Instant) -> Outcome<Step> {
+    let step = match timeout {
+        Some(limit) => {
+            steps.recv_timeout((since + limit).saturating_duration_since(Instant::now()))
+        }
+        None => steps.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match step {
+        Ok(step) => Outcome::Done(step),
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(timeout.unwrap()),
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panicked("stopped early".to_owned()),
+    }
+}
+
+type Solved = (Outcome<String>, Duration, alloc::Allocs);
+
+/// What to report for a part whose step never came.
+fn unsolved(outcome: Outcome<Step>) -> Solved {
+    match outcome {
+        Outcome::TimedOut(limit) => (Outcome::TimedOut(limit), limit, Default::default()),
+        Outcome::Panicked(message) => (
+            Outcome::Panicked(message),
+            Duration::ZERO,
+            Default::default(),
+        ),
+        Outcome::Done(_) => unreachable!("a step came after all"),
+    }
+}
+
+/// Like `solve_timed`, except that every phase runs on another thread, where a panic is caught and
+/// one that takes longer than `timeout` is given up on. Threads can't be stopped, so one that
+/// never finishes keeps a core busy until the process exits. If part 1 times out, part 2 gets a
+/// thread of its own, where the input is parsed again.
+pub fn solve<S: Solution + Send + 'static>(
+    solution: &Arc<S>,
+    input_data: &str,
+    parallel: bool,
+    timeout: Option<Duration>,
+) -> Report {
+    let input_data = Arc::<str>::from(input_data);
+    let has_part2 = solution.has_part2();
+    let parts: &'static [usize] = if has_part2 { &[1, 2] } else { &[1] };
+    // Parts running at once would count each other's allocations
+    let parallel = parallel && has_part2 && !alloc::enabled();
+    let steps = spawn(solution, &input_data, parts, parallel);
+
+    let (parsed, parse_time, parse_allocs) = match next(&steps, timeout, Instant::now()) {
+        Outcome::Done(Step::Parsed(parsed)) => parsed,
+        Outcome::Done(Step::Solved(..)) => unreachable!("solved before parsing"),
+        Outcome::Panicked(message) => return Outcome::Panicked(message),
+        Outcome::TimedOut(limit) => return Outcome::TimedOut(limit),
+    };
+    match parsed {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Outcome::Done(Err(e)),
+        Err(message) => return Outcome::Panicked(message),
+    }
+
+    // Parts running at once share a deadline, and otherwise each one's starts when the last ends
+    let mut solved = [None, None];
+    let mut since = Instant::now();
+    let mut stopped = None;
+    while stopped.is_none() && solved.iter().flatten().count() < parts.len() {
+        match next(&steps, timeout, since) {
+            Outcome::Done(Step::Solved(part, (answer, time, allocs))) => {
+                let answer = answer.map_or_else(Outcome::Panicked, Outcome::Done);
+                solved[part - 1] = Some((answer, time, allocs));
+                if !parallel {
+                    since = Instant::now();
+                }
+            }
+            Outcome::Done(Step::Parsed(_)) => unreachable!("parsed twice"),
+            outcome => stopped = Some(unsolved(outcome)),
+        }
+    }
+    if !parallel && has_part2 && solved[0].is_none() {
+        if let Some((Outcome::TimedOut(_), ..)) = stopped {
+            solved[1] = Some(retry_part2(solution, &input_data, timeout));
+        }
+    }
+    let [part1, part2] = solved.map(|part| part.or_else(|| stopped.clone()));
+    let (part1, part1_time, part1_allocs) = part1.unwrap();
+    let (part2, part2_time, part2_allocs) = match part2.filter(|_| has_part2) {
+        Some((part2, time, allocs)) => (Some(part2), time, allocs),
+        None => (None, Duration::ZERO, Default::default()),
+    };
+
+    Outcome::Done(Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse: parse_time,
+            part1: part1_time,
+            part2: part2_time,
+        },
+        memory: alloc::enabled().then_some(alloc::Usage {
+            parse: parse_allocs,
+            part1: part1_allocs,
+            part2: part2_allocs,
+        }),
+    }))
+}
+
+/// Part 2 on a thread of its own, for when part 1 never finished and so part 2 never started.
+fn retry_part2<S: Solution + Send + 'static>(
+    solution: &Arc<S>,
+    input_data: &Arc<str>,
+    timeout: Option<Duration>,
+) -> Solved {
+    let steps = spawn(solution, input_data, &[2], false);
+    loop {
+        return match next(&steps, timeout, Instant::now()) {
+            Outcome::Done(Step::Parsed(_)) => continue,
+            Outcome::Done(Step::Solved(_, (answer, time, allocs))) => (
+                answer.map_or_else(Outcome::Panicked, Outcome::Done),
+                time,
+                allocs,
+            ),
+            outcome => unsolved(outcome),
+        };
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn isolation() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PARSES: AtomicUsize = AtomicUsize::new(0);
+    let solution = Arc::new(crate::Lines::new(
+        |s| {
+            PARSES.fetch_add(1, Ordering::Relaxed);
+            crate::parse_unwrap::<u32>(s)
+        },
+        |_: &[u32]| -> u32 { panic!("no part 1") },
+        |input: &[u32]| loop {
+            if input.is_empty() {
+                break 0;
+            }
+            std::thread::yield_now();
+        },
+    ));
+    let limit = Duration::from_millis(50);
+    for parallel in [false, true] {
+        let Outcome::Done(Ok(answers)) = solve(&solution, "1\n2", parallel, Some(limit)) else {
+            panic!("didn't parse");
+        };
+        assert_eq!(answers.part1, Outcome::Panicked("no part 1".to_owned()));
+        assert_eq!(answers.part2, Some(Outcome::TimedOut(limit)));
+        assert_eq!(
+            answers.part2.unwrap().to_string(),
+            "timed out after 50.00 ms"
+        );
+    }
+    // Each line once per run, however many parts are running
+    assert_eq!(PARSES.load(Ordering::Relaxed), 4);
+}
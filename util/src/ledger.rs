@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use crate::examples::multi_line;
+use crate::{normalize_answer, parse_expected, InputSource};
+
+/// Accepted answers for a real input, kept beside it as `answers.txt` (or `answers.NAME.txt` for
+/// `input.NAME.txt`) in the same `part1:`/`part2:` format as the examples.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ledger {
+    pub path: PathBuf,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Ledger {
+    pub fn path_for(source: &InputSource, dir: &Path) -> Result<PathBuf, String> {
+        match source {
+            InputSource::Default => Ok(dir.join("answers.txt")),
+            InputSource::Named(name) => Ok(dir.join(format!("answers.{}.txt", name))),
+            _ => Err(format!(
+                "answers can only be recorded for `input.txt` or `--input NAME`, not {}",
+                source.label_in(dir).display()
+            )),
+        }
+    }
+
+    /// An empty ledger if nothing has been recorded yet.
+    pub fn load(source: &InputSource, dir: &Path) -> Result<Self, String> {
+        let path = Self::path_for(source, dir)?;
+        let (part1, part2) = match std::fs::read_to_string(&path) {
+            Ok(data) => parse_expected(&data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (None, None),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+        Ok(Self { path, part1, part2 })
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part1.is_none() && self.part2.is_none()
+    }
+
+    pub fn check(&self, part: u8, actual: &str) -> Result<(), String> {
+        let actual = normalize_answer(actual);
+        match self.expected(part) {
+            Some(expected) if expected != actual => Err(format!(
+                "MISMATCH: part {} is {}, but {} says {}",
+                part,
+                multi_line(&actual),
+                self.path.display(),
+                multi_line(expected)
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks both answers, describing what was and wasn't verified.
+    pub fn verify(&self, answers: &[Option<String>; 2]) -> Result<String, String> {
+        if self.is_empty() {
+            return Err(format!(
+                "no answers recorded in {}; accept them with `--record`",
+                self.path.display()
+            ));
+        }
+
+        let mut mismatches = vec![];
+        let mut statuses = vec![];
+        for (part, actual) in (1..=2).zip(answers) {
+            let Some(actual) = actual else { continue };
+            let status = match self.check(part, actual) {
+                Ok(()) if self.expected(part).is_none() => "not recorded",
+                Ok(()) => "ok",
+                Err(e) => {
+                    mismatches.push(e);
+                    "MISMATCH"
+                }
+            };
+            statuses.push(format!("part {} {}", part, status));
+        }
+
+        if mismatches.is_empty() {
+            Ok(format!("{}: {}", self.path.display(), statuses.join(", ")))
+        } else {
+            Err(mismatches.join("\n"))
+        }
+    }
+
+    /// Fills in any parts that haven't been recorded yet. Answers that are already recorded are
+    /// never changed here; edit the file by hand if one of them was wrong.
+    pub fn record(&mut self, answers: &[Option<String>; 2]) -> Result<String, String> {
+        for (part, actual) in (1..=2).zip(answers) {
+            if let Some(actual) = actual {
+                self.check(part, actual)?;
+            }
+        }
+
+        let mut recorded = vec![];
+        for ((part, slot), actual) in [(1, &mut self.part1), (2, &mut self.part2)]
+            .into_iter()
+            .zip(answers)
+        {
+            if let (None, Some(actual)) = (&slot, actual) {
+                *slot = Some(normalize_answer(actual));
+                recorded.push(format!("part {}", part));
+            }
+        }
+        if recorded.is_empty() {
+            return Ok(format!("{}: already up to date", self.path.display()));
+        }
+
+        std::fs::write(&self.path, self.to_string())
+            .map_err(|e| format!("couldn't write {}: {}", self.path.display(), e))?;
+        Ok(format!(
+            "{}: recorded {}",
+            self.path.display(),
+            recorded.join(" and ")
+        ))
+    }
+}
+
+impl std::fmt::Display for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (part, answer) in [(1, &self.part1), (2, &self.part2)] {
+            match answer {
+                Some(answer) if answer.contains('\n') => writeln!(f, "part{}:\n{}", part, answer)?,
+                Some(answer) => writeln!(f, "part{}: {}", part, answer)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn ledger() {
+    let mut ledger = Ledger {
+        path: "answers.txt".into(),
+        part1: Some("15".into()),
+        part2: None,
+    };
+    let answers = [Some("15".to_owned()), Some("1134".to_owned())];
+    assert_eq!(
+        ledger.verify(&answers),
+        Ok("answers.txt: part 1 ok, part 2 not recorded".to_owned())
+    );
+
+    let wrong = [Some("16".to_owned()), Some("1134".to_owned())];
+    assert_eq!(
+        ledger.verify(&wrong),
+        Err("MISMATCH: part 1 is 16, but answers.txt says 15".to_owned())
+    );
+    assert!(ledger.record(&wrong).is_err());
+
+    ledger.part2 = Some("1134".into());
+    assert_eq!(ledger.to_string(), "part1: 15\npart2: 1134\n");
+}
@@ -1,251 +1,965 @@
-#![feature(test)]
-
-extern crate test;
-
-use std::fmt::{Debug, Display};
+use std::fmt::Display;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
+
+pub mod alloc;
+pub mod alt;
+pub mod bench;
+pub mod check;
+mod cli;
+mod error;
+mod examples;
+pub mod generate;
+pub mod geom;
+pub mod grid;
+mod input;
+pub mod isolate;
+mod ledger;
+pub mod ocr;
+pub mod parallel;
+pub mod puzzle;
+pub mod reduce;
+pub mod scan;
+pub mod search;
+pub mod stream;
+pub mod timing;
+pub mod viz;
+pub use cli::Args;
+pub use error::ParseError;
+pub use examples::{check_example, load_examples, parse_expected, test, Example};
+pub use grid::Grid;
+pub use input::InputSource;
+pub use ledger::Ledger;
+pub use puzzle::Puzzle;
+pub use timing::Timings;
+
+pub fn parse<T>(s: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    let s = s.trim();
+    s.parse().map_err(|e: T::Err| e.into().within(s, s))
+}
 
 pub fn parse_unwrap<T>(s: &str) -> T
 where
     T: FromStr,
-    T::Err: Debug,
+    T::Err: Into<ParseError>,
 {
-    s.trim().parse().unwrap()
+    parse(s).unwrap_or_else(|e| panic!("{}", e))
 }
 
-pub fn parse_input<P: AsRef<Path>, T, F: FnMut(&str) -> T>(input_path: P, mut f: F) -> Vec<T> {
-    std::fs::read_to_string(input_path)
-        .unwrap()
-        .lines()
-        .map(|line| f(line.trim()))
-        .collect()
+pub fn parse_lines<T, F: FnMut(&str) -> T>(input: &str, f: F) -> Vec<T> {
+    input.lines().map(str::trim).map(f).collect()
 }
 
-pub fn run<Parser, Part1, Part2, In, Out>(parser: Parser, part1: Part1, part2: Part2)
+pub fn try_parse_lines<T, E, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
 where
-    Parser: FnMut(&str) -> In,
-    Part1: FnOnce(&[In]) -> Out,
-    Part2: FnOnce(&[In]) -> Out,
-    Out: Display,
+    E: Into<ParseError>,
+    F: FnMut(&str) -> Result<T, E>,
 {
-    let input = parse_input("input.txt", parser);
-    println!("{}\n{}", part1(&input), part2(&input));
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let trimmed = line.trim();
+            f(trimmed).map_err(|e| e.into().within(line, trimmed).with_line(i + 1))
+        })
+        .collect()
 }
 
-pub fn run_alt<Parser, Part1, Part2, In, Out>(parser: Parser, part1: Part1, part2: Part2)
-where
-    Parser: FnOnce(&str) -> In,
-    Part1: FnOnce(&In) -> Out,
-    Part2: FnOnce(&In) -> Out,
-    Out: Display,
-{
-    let input_data = std::fs::read_to_string("input.txt").unwrap();
-    let input = parser(&input_data);
-    println!("{}\n{}", part1(&input), part2(&input));
+pub fn parse_input<P: AsRef<Path>, T, F: FnMut(&str) -> T>(input_path: P, f: F) -> Vec<T> {
+    parse_lines(&std::fs::read_to_string(input_path).unwrap(), f)
 }
 
-pub fn test<Parser, Part1, Part2, In, Out>(parser: Parser, part1: Part1, part2: Part2)
+pub fn try_parse_input<P, T, E, F>(input_path: P, f: F) -> Result<Vec<T>, ParseError>
 where
-    Parser: FnMut(&str) -> In,
-    Part1: FnOnce(&[In]) -> Out,
-    Part2: FnOnce(&[In]) -> Out,
-    Out: Debug + FromStr + PartialEq,
-    Out::Err: Debug,
+    P: AsRef<Path>,
+    E: Into<ParseError>,
+    F: FnMut(&str) -> Result<T, E>,
 {
-    let input = parse_input("test.txt", parser);
-    let (x, y) = parse_output::<Out>();
+    let input_data =
+        std::fs::read_to_string(&input_path).map_err(|e| ParseError::unreadable(&input_path, e))?;
+    try_parse_lines(&input_data, f).map_err(|e| e.with_file(input_path))
+}
+
+/// `Sync` so that both parts can run at once on the same input with `--parallel`.
+pub trait Solution: Sync {
+    type Input: Sync;
+    type Output1: Display + Send;
+    type Output2: Display + Send;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Self::Output1;
+    fn part2(&self, input: &Self::Input) -> Self::Output2;
+
+    /// Day 25 only has the one puzzle.
+    fn has_part2(&self) -> bool {
+        true
+    }
+
+    /// What `--visualize` shows, for days with a simulation worth watching.
+    fn frames(&self, _input: &Self::Input) -> Option<viz::Frames> {
+        None
+    }
+
+    /// Checks the input against what the solution assumes about it, for `--check`.
+    fn check(&self, _input: &Self::Input) -> check::Report {
+        check::Report::default()
+    }
+
+    /// A made-up input of about `size`, the same for the same `seed`, for days with a generator.
+    fn generate(&self, _seed: u64, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Other implementations of the parts, as (part, name), for cross-checking and benching.
+    fn alternatives(&self) -> Vec<(u8, &'static str)> {
+        vec![]
+    }
+
+    /// Runs the `i`th of `alternatives`.
+    fn alternative(&self, _i: usize, _input: &Self::Input) -> String {
+        panic!("there are no alternatives")
+    }
 
-    assert_eq!(part1(&input), x);
-    assert_eq!(part2(&input), y);
+    /// Solves the file at `path` without reading it all in, for days that can, with `--stream`.
+    fn stream(&self, _path: &Path) -> Option<Solved<Self::Output1, Self::Output2>> {
+        None
+    }
 }
 
-pub fn test_alt<Parser, Part1, Part2, In, Out>(parser: Parser, part1: Part1, part2: Part2)
-where
-    Parser: FnOnce(&str) -> In,
-    Part1: FnOnce(&In) -> Out,
-    Part2: FnOnce(&In) -> Out,
-    Out: Debug + FromStr + PartialEq,
-    Out::Err: Debug,
-{
-    let input_data = std::fs::read_to_string("test.txt").unwrap();
-    let input = parser(&input_data);
-    let (x, y) = parse_output::<Out>();
+/// The answer to a part that doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoPart2;
 
-    assert_eq!(part1(&input), x);
-    assert_eq!(part2(&input), y);
+impl Display for NoPart2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(no part 2)")
+    }
 }
 
-pub fn parse_output<T>() -> (T, T)
-where
-    T: FromStr,
-    T::Err: Debug,
-{
-    let output_data = std::fs::read_to_string("test.out.txt").unwrap();
-    let (a, b) = output_data.split_once("\n").unwrap();
-    let [x, y] = [a, b].map(parse_unwrap::<T>);
-    (x, y)
+pub fn no_part2<T: ?Sized>(_: &T) -> NoPart2 {
+    NoPart2
 }
 
-pub fn bench_parse<Parser, In>(mut parser: Parser, b: &mut test::Bencher)
+type Parser<In> = Box<dyn Fn(&str) -> Result<In, ParseError> + Send + Sync>;
+type FramesFn<In> = Box<dyn Fn(&In) -> viz::Frames + Send + Sync>;
+
+fn frames_fn<In: ?Sized, F, I>(frames: F) -> FramesFn<In>
 where
-    Parser: FnMut(&str) -> In,
+    F: Fn(&In) -> I + Send + Sync + 'static,
+    I: IntoIterator,
+    I::IntoIter: 'static,
+    I::Item: viz::Visualize,
 {
-    let input_data = std::fs::read_to_string("input.txt").unwrap();
-    let input_data = input_data.as_str();
-    b.iter(|| {
-        let input_data = test::black_box(input_data);
-        for line in input_data.lines().map(str::trim) {
-            test::black_box(parser(line));
-        }
+    Box::new(move |input| {
+        Box::new(
+            frames(input)
+                .into_iter()
+                .map(|state| viz::Visualize::frame(&state)),
+        )
     })
 }
 
-pub fn bench_parse_alt<Parser, In>(mut parser: Parser, b: &mut test::Bencher)
+/// `Clone` and `Copy` for structs of function pointers over `In`, which deriving them would only
+/// give when `In: Clone`.
+macro_rules! copy_fn_structs {
+    ($($($path:ident)::+),*) => {
+        $(
+            impl<In: ?Sized> Clone for $($path)::+<In> {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl<In: ?Sized> Copy for $($path)::+<In> {}
+        )*
+    };
+}
+
+copy_fn_structs!(alt::Alternative, check::Invariant);
+
+/// What `Lines` and `Whole` have besides a parser and the parts, which take `In`.
+struct Extras<In: ?Sized> {
+    has_part2: bool,
+    frames: Option<FramesFn<In>>,
+    invariants: Vec<check::Invariant<In>>,
+    generator: Option<generate::Generator>,
+    alternatives: Vec<alt::Alternative<In>>,
+}
+
+impl<In: ?Sized> Default for Extras<In> {
+    fn default() -> Self {
+        Self {
+            has_part2: true,
+            frames: None,
+            invariants: vec![],
+            generator: None,
+            alternatives: vec![],
+        }
+    }
+}
+
+impl<In: ?Sized> Extras<In> {
+    fn frames(&self, input: &In) -> Option<viz::Frames> {
+        self.frames.as_ref().map(|frames| frames(input))
+    }
+
+    fn alternatives(&self) -> Vec<(u8, &'static str)> {
+        self.alternatives.iter().map(|a| (a.part, a.name)).collect()
+    }
+
+    fn alternative(&self, i: usize, input: &In) -> String {
+        (self.alternatives[i].run)(input)
+    }
+
+    fn check(&self, input: &In) -> check::Report {
+        check::Report::run(&self.invariants, input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let generator = self.generator?;
+        Some(generator(&mut generate::Rng::new(seed), size))
+    }
+}
+
+/// The builder methods of `Lines` and `Whole`, whose parts take `$input`.
+macro_rules! builders {
+    ($ty:ident, $input:ty) => {
+        impl<In, Part1, Part2> $ty<In, Part1, Part2> {
+            pub fn without_part2(mut self) -> Self {
+                self.extras.has_part2 = false;
+                self
+            }
+
+            pub fn with_frames<F, I>(mut self, frames: F) -> Self
+            where
+                F: Fn(&$input) -> I + Send + Sync + 'static,
+                I: IntoIterator,
+                I::IntoIter: 'static,
+                I::Item: viz::Visualize,
+            {
+                self.extras.frames = Some(frames_fn(frames));
+                self
+            }
+
+            pub fn with_alternatives(mut self, alternatives: &[alt::Alternative<$input>]) -> Self {
+                self.extras.alternatives = alternatives.to_vec();
+                self
+            }
+
+            pub fn with_invariants(mut self, invariants: &[check::Invariant<$input>]) -> Self {
+                self.extras.invariants = invariants.to_vec();
+                self
+            }
+
+            pub fn with_generator(mut self, generator: generate::Generator) -> Self {
+                self.extras.generator = Some(generator);
+                self
+            }
+        }
+    };
+}
+
+/// A solution whose parser is applied to each line of the input.
+pub struct Lines<In, Part1, Part2> {
+    parser: Parser<In>,
+    part1: Part1,
+    part2: Part2,
+    extras: Extras<[In]>,
+}
+
+impl<In, Part1, Part2> Lines<In, Part1, Part2> {
+    pub fn new<P, Out1, Out2>(parser: P, part1: Part1, part2: Part2) -> Self
+    where
+        P: Fn(&str) -> In + Send + Sync + 'static,
+        Part1: Fn(&[In]) -> Out1,
+        Part2: Fn(&[In]) -> Out2,
+    {
+        Self::try_new(move |s| Ok::<_, ParseError>(parser(s)), part1, part2)
+    }
+
+    pub fn try_new<P, E, Out1, Out2>(parser: P, part1: Part1, part2: Part2) -> Self
+    where
+        P: Fn(&str) -> Result<In, E> + Send + Sync + 'static,
+        E: Into<ParseError>,
+        Part1: Fn(&[In]) -> Out1,
+        Part2: Fn(&[In]) -> Out2,
+    {
+        Self {
+            parser: Box::new(move |s| parser(s).map_err(Into::into)),
+            part1,
+            part2,
+            extras: Extras::default(),
+        }
+    }
+}
+
+builders!(Lines, [In]);
+
+impl<In, Part1, Part2, Out1, Out2> Solution for Lines<In, Part1, Part2>
 where
-    Parser: FnMut(&str) -> In,
+    In: Sync,
+    Part1: Fn(&[In]) -> Out1 + Sync,
+    Part2: Fn(&[In]) -> Out2 + Sync,
+    Out1: Display + Send,
+    Out2: Display + Send,
 {
-    let input_data = std::fs::read_to_string("input.txt").unwrap();
-    let input_data = input_data.as_str();
-    b.iter(|| {
-        let input_data = test::black_box(input_data);
-        test::black_box(parser(input_data));
-    })
+    type Input = Vec<In>;
+    type Output1 = Out1;
+    type Output2 = Out2;
+
+    fn parse(&self, input: &str) -> Result<Vec<In>, ParseError> {
+        try_parse_lines(input, &self.parser)
+    }
+
+    fn part1(&self, input: &Vec<In>) -> Out1 {
+        (self.part1)(input)
+    }
+
+    fn part2(&self, input: &Vec<In>) -> Out2 {
+        (self.part2)(input)
+    }
+
+    fn has_part2(&self) -> bool {
+        self.extras.has_part2
+    }
+
+    fn frames(&self, input: &Vec<In>) -> Option<viz::Frames> {
+        self.extras.frames(input)
+    }
+
+    fn alternatives(&self) -> Vec<(u8, &'static str)> {
+        self.extras.alternatives()
+    }
+
+    fn alternative(&self, i: usize, input: &Vec<In>) -> String {
+        self.extras.alternative(i, input)
+    }
+
+    fn check(&self, input: &Vec<In>) -> check::Report {
+        self.extras.check(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        self.extras.generate(seed, size)
+    }
+}
+
+/// A solution whose parser is handed the whole input at once.
+pub struct Whole<In, Part1, Part2> {
+    parser: Parser<In>,
+    part1: Part1,
+    part2: Part2,
+    extras: Extras<In>,
+}
+
+impl<In, Part1, Part2> Whole<In, Part1, Part2> {
+    pub fn new<P, Out1, Out2>(parser: P, part1: Part1, part2: Part2) -> Self
+    where
+        P: Fn(&str) -> In + Send + Sync + 'static,
+        Part1: Fn(&In) -> Out1,
+        Part2: Fn(&In) -> Out2,
+    {
+        Self::try_new(move |s| Ok::<_, ParseError>(parser(s)), part1, part2)
+    }
+
+    pub fn try_new<P, E, Out1, Out2>(parser: P, part1: Part1, part2: Part2) -> Self
+    where
+        P: Fn(&str) -> Result<In, E> + Send + Sync + 'static,
+        E: Into<ParseError>,
+        Part1: Fn(&In) -> Out1,
+        Part2: Fn(&In) -> Out2,
+    {
+        Self {
+            parser: Box::new(move |s| parser(s).map_err(Into::into)),
+            part1,
+            part2,
+            extras: Extras::default(),
+        }
+    }
 }
 
-pub fn bench_solution<Parser, F, In, Out>(parser: Parser, mut solution: F, b: &mut test::Bencher)
+builders!(Whole, In);
+
+impl<In, Part1, Part2, Out1, Out2> Solution for Whole<In, Part1, Part2>
 where
-    Parser: FnMut(&str) -> In,
-    F: FnMut(&[In]) -> Out,
+    In: Sync,
+    Part1: Fn(&In) -> Out1 + Sync,
+    Part2: Fn(&In) -> Out2 + Sync,
+    Out1: Display + Send,
+    Out2: Display + Send,
 {
-    let input = parse_input("input.txt", parser);
-    b.iter(|| {
-        let input = test::black_box(&input);
-        test::black_box(solution(input));
-    })
+    type Input = In;
+    type Output1 = Out1;
+    type Output2 = Out2;
+
+    fn parse(&self, input: &str) -> Result<In, ParseError> {
+        (self.parser)(input)
+    }
+
+    fn part1(&self, input: &In) -> Out1 {
+        (self.part1)(input)
+    }
+
+    fn part2(&self, input: &In) -> Out2 {
+        (self.part2)(input)
+    }
+
+    fn has_part2(&self) -> bool {
+        self.extras.has_part2
+    }
+
+    fn frames(&self, input: &In) -> Option<viz::Frames> {
+        self.extras.frames(input)
+    }
+
+    fn alternatives(&self) -> Vec<(u8, &'static str)> {
+        self.extras.alternatives()
+    }
+
+    fn alternative(&self, i: usize, input: &In) -> String {
+        self.extras.alternative(i, input)
+    }
+
+    fn check(&self, input: &In) -> check::Report {
+        self.extras.check(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        self.extras.generate(seed, size)
+    }
 }
 
-pub fn bench_solution_alt<Parser, F, In, Out>(
-    parser: Parser,
-    mut solution: F,
-    b: &mut test::Bencher,
-) where
-    Parser: FnOnce(&str) -> In,
-    F: FnMut(&In) -> Out,
-{
-    let input_data = std::fs::read_to_string("input.txt").unwrap();
-    let input = parser(&input_data);
-    b.iter(|| {
-        let input = test::black_box(&input);
-        test::black_box(solution(input));
+pub fn solve<S: Solution>(
+    solution: &S,
+    input_data: &str,
+) -> Result<(S::Output1, Option<S::Output2>), ParseError> {
+    let input = solution.parse(input_data)?;
+    let part2 = solution.has_part2().then(|| solution.part2(&input));
+    Ok((solution.part1(&input), part2))
+}
+
+/// Makes answers comparable with what's written in `.out` and `answers.txt` files, ignoring
+/// trailing whitespace and surrounding blank lines, so that multi-line answers work too.
+pub fn normalize_answer(answer: &str) -> String {
+    let lines = answer.lines().map(str::trim_end).collect::<Vec<_>>();
+    lines.join("\n").trim_matches('\n').to_owned()
+}
+
+pub type Solved<Out1, Out2> = Result<Answers<Out1, Out2>, ParseError>;
+
+pub struct Answers<Out1, Out2 = Out1> {
+    pub part1: Out1,
+    pub part2: Option<Out2>,
+    pub timings: Timings,
+    /// Only counted with the `count-alloc` feature.
+    pub memory: Option<alloc::Usage>,
+}
+
+fn print_answer(answer: &str, time: std::time::Duration) {
+    let time = timing::format_duration(time);
+    if answer.contains('\n') {
+        println!("({})\n{}", time, answer.trim_end());
+    } else {
+        println!("{:<24} ({})", answer, time);
+    }
+}
+
+impl<Out1: Display, Out2: Display> Answers<Out1, Out2> {
+    pub fn strings(&self) -> [Option<String>; 2] {
+        [
+            Some(self.part1.to_string()),
+            self.part2.as_ref().map(Out2::to_string),
+        ]
+    }
+
+    /// For when the answers have to outlive the solution's concrete type.
+    pub fn to_strings(&self) -> Answers<String> {
+        Answers {
+            part1: self.part1.to_string(),
+            part2: self.part2.as_ref().map(Out2::to_string),
+            timings: self.timings,
+            memory: self.memory,
+        }
+    }
+
+    pub fn print(&self) {
+        let t = &self.timings;
+        print_answer(&self.part1.to_string(), t.part1);
+        if let Some(part2) = &self.part2 {
+            print_answer(&part2.to_string(), t.part2);
+        }
+        let fmt = timing::format_duration;
+        println!("parsed in {}, {} total", fmt(t.parse), fmt(t.total()));
+        if let Some(memory) = &self.memory {
+            for (phase, allocs) in memory.phases() {
+                println!("{:<6} {}", phase, allocs);
+            }
+        }
+    }
+}
+
+fn phase<T>(f: impl FnOnce() -> T) -> ((T, std::time::Duration), alloc::Allocs) {
+    alloc::measure(|| timing::time(f))
+}
+
+/// With `parallel`, part 2 runs on another thread at the same time as part 1, unless allocations
+/// are being counted.
+pub fn solve_timed<S: Solution>(
+    solution: &S,
+    input_data: &str,
+    parallel: bool,
+) -> Result<Answers<S::Output1, S::Output2>, ParseError> {
+    let ((input, parse), parse_allocs) = phase(|| solution.parse(input_data));
+    let input = input?;
+    let part1 = || phase(|| solution.part1(&input));
+    let part2 = || match solution.has_part2() {
+        true => phase(|| Some(solution.part2(&input))),
+        false => Default::default(),
+    };
+    let (((part1, part1_time), part1_allocs), ((part2, part2_time), part2_allocs)) =
+        if parallel && !alloc::enabled() {
+            std::thread::scope(|scope| {
+                let part2 = scope.spawn(part2);
+                let part1 = part1();
+                let part2 = part2
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e));
+                (part1, part2)
+            })
+        } else {
+            (part1(), part2())
+        };
+    Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+        memory: alloc::enabled().then_some(alloc::Usage {
+            parse: parse_allocs,
+            part1: part1_allocs,
+            part2: part2_allocs,
+        }),
     })
 }
 
+fn read_source(source: &InputSource, dir: &Path) -> Result<String, ParseError> {
+    source
+        .read_in(dir)
+        .map_err(|e| ParseError::unreadable(source.label_in(dir), e))
+}
+
+/// Solves `source`, resolving relative paths against `dir`.
+pub fn try_solve_source<S: Solution>(
+    solution: S,
+    source: &InputSource,
+    dir: &Path,
+    parallel: bool,
+) -> Result<Answers<S::Output1, S::Output2>, ParseError> {
+    let input_data = read_source(source, dir)?;
+    solve_timed(&solution, &input_data, parallel).map_err(|e| e.with_file(source.label_in(dir)))
+}
+
+/// Like `try_solve_source`, but a panic or a phase that runs past `timeout` is reported rather than
+/// taking the caller down with it.
+pub fn solve_source_isolated<S: Solution + Send + 'static>(
+    solution: S,
+    source: &InputSource,
+    dir: &Path,
+    parallel: bool,
+    timeout: Option<std::time::Duration>,
+) -> isolate::Report {
+    let input_data = match read_source(source, dir) {
+        Ok(input_data) => input_data,
+        Err(e) => return isolate::Outcome::Done(Err(e)),
+    };
+    match isolate::solve(&Arc::new(solution), &input_data, parallel, timeout) {
+        isolate::Outcome::Done(Err(e)) => {
+            isolate::Outcome::Done(Err(e.with_file(source.label_in(dir))))
+        }
+        report => report,
+    }
+}
+
+/// Like `try_solve_source`, but prints the answers too.
+pub fn try_run_source<S: Solution>(
+    solution: S,
+    source: &InputSource,
+    dir: &Path,
+) -> Result<Answers<S::Output1, S::Output2>, ParseError> {
+    let answers = try_solve_source(solution, source, dir, false)?;
+    answers.print();
+    Ok(answers)
+}
+
+/// `--record`s and then `--verify`s answers against the ledger for `source`.
+pub fn check_ledger(
+    answers: &[Option<String>; 2],
+    source: &InputSource,
+    dir: &Path,
+    record: bool,
+    verify: bool,
+) -> Result<(), String> {
+    if !record && !verify {
+        return Ok(());
+    }
+    let mut ledger = Ledger::load(source, dir)?;
+    if record {
+        eprintln!("{}", ledger.record(answers)?);
+    }
+    if verify {
+        eprintln!("{}", ledger.verify(answers)?);
+    }
+    Ok(())
+}
+
+pub fn try_run_file<S: Solution, P: AsRef<Path>>(
+    solution: S,
+    input_path: P,
+) -> Result<Answers<S::Output1, S::Output2>, ParseError> {
+    let source = InputSource::Path(input_path.as_ref().to_owned());
+    try_run_source(solution, &source, Path::new(""))
+}
+
+fn exit_on_error<T>(result: Result<T, ParseError>) {
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+pub fn run_file<S: Solution, P: AsRef<Path>>(solution: S, input_path: P) {
+    exit_on_error(try_run_file(solution, input_path));
+}
+
+/// Benchmarks against `source` instead of printing the answers.
+pub fn try_bench_source<S: Solution>(
+    name: &str,
+    solution: S,
+    source: &InputSource,
+    dir: &Path,
+) -> Result<Vec<bench::Measurement>, ParseError> {
+    let input_data = read_source(source, dir)?;
+    bench::bench_solution(name, &solution, &input_data, &bench::Config::from_env())
+        .map_err(|e| e.with_file(source.label_in(dir)))
+}
+
+fn visualize<S: Solution>(
+    solution: S,
+    source: &InputSource,
+    dir: &Path,
+    options: &viz::Options,
+) -> Result<(), ParseError> {
+    let input_data = read_source(source, dir)?;
+    let input = solution
+        .parse(&input_data)
+        .map_err(|e| e.with_file(source.label_in(dir)))?;
+    let Some(frames) = solution.frames(&input) else {
+        eprintln!("nothing to visualize for this day");
+        std::process::exit(1);
+    };
+    match viz::play(frames, options) {
+        Ok(n) => eprintln!("{} frames", n),
+        Err(e) => {
+            eprintln!("couldn't visualize: {}", e);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Parses `source` and checks it against the solution's invariants.
+pub fn check_source<S: Solution>(
+    solution: &S,
+    source: &InputSource,
+    dir: &Path,
+) -> Result<check::Report, ParseError> {
+    let input_data = read_source(source, dir)?;
+    let input = solution
+        .parse(&input_data)
+        .map_err(|e| e.with_file(source.label_in(dir)))?;
+    Ok(solution.check(&input))
+}
+
+/// Parses `source` and compares the answers of every implementation of each part.
+pub fn cross_check_source<S: Solution>(
+    solution: &S,
+    source: &InputSource,
+    dir: &Path,
+) -> Result<alt::CrossCheck, ParseError> {
+    let input_data = read_source(source, dir)?;
+    let input = solution
+        .parse(&input_data)
+        .map_err(|e| e.with_file(source.label_in(dir)))?;
+    Ok(alt::CrossCheck::run(solution, &input))
+}
+
+/// Shrinks `source` while it keeps failing the same way, and saves what's left as a new example.
+fn reduce_source<S: Solution + Send + 'static>(
+    solution: S,
+    failure: reduce::Failure,
+    source: &InputSource,
+    dir: &Path,
+) -> Result<(), ParseError> {
+    let input_data = read_source(source, dir)?;
+    let solution = Arc::new(solution);
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let reduced = reduce::reduce(
+        &input_data,
+        |s| failure.happens(&solution, s),
+        |granularity, len| eprintln!("{:?}: down to {} bytes", granularity, len),
+    );
+    let confirmed = reduced.as_ref().map(|s| failure.confirmed(&solution, s));
+    std::panic::set_hook(hook);
+
+    let (Some(reduced), Some((confirmed, running))) = (reduced, confirmed) else {
+        eprintln!("{} doesn't {}", source.label_in(dir).display(), failure);
+        std::process::exit(1);
+    };
+    if running > 0 {
+        eprintln!(
+            "{} parts that timed out are still running, so the reproducer may be slower for it",
+            running
+        );
+    }
+    if !confirmed {
+        eprintln!(
+            "{} bytes down to {}, but that doesn't {} on its own:\n{}",
+            input_data.len(),
+            reduced.len(),
+            failure,
+            reduced
+        );
+        std::process::exit(1);
+    }
+    let examples = dir.join("examples");
+    let path = (1..)
+        .map(|i| match i {
+            1 => examples.join("reduced.in"),
+            _ => examples.join(format!("reduced{}.in", i)),
+        })
+        .find(|path| !path.exists())
+        .unwrap();
+    let written = std::fs::create_dir_all(&examples)
+        .and_then(|()| std::fs::write(&path, &reduced))
+        .and_then(|()| std::fs::write(path.with_extension("out"), ""));
+    if let Err(e) = written {
+        eprintln!("couldn't write {}: {}", path.display(), e);
+        std::process::exit(1);
+    }
+    print!("{}", reduced);
+    eprintln!(
+        "{} bytes down to {}, written to {}; put the right answers in {}",
+        input_data.len(),
+        reduced.len(),
+        path.display(),
+        path.with_extension("out").display()
+    );
+    Ok(())
+}
+
+/// Prints a made-up input, or writes it where `--input NAME` would read it from `dir`.
+pub fn write_generated(input: &str, source: &InputSource, dir: &Path) -> Result<(), String> {
+    let InputSource::Named(name) = source else {
+        print!("{}", input);
+        return Ok(());
+    };
+    let path = source.label_in(dir);
+    std::fs::write(&path, input)
+        .map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+    eprintln!("wrote {} (`--input {}`)", path.display(), name);
+    Ok(())
+}
+
+pub fn try_run<S: Solution + Send + 'static>(solution: S) -> Result<(), ParseError> {
+    try_run_with(solution, &Args::from_env(), Path::new(""))
+}
+
+/// Does what `args` ask for with the input in `dir`, the way a day's own binary would.
+pub fn try_run_with<S: Solution + Send + 'static>(
+    solution: S,
+    args: &Args,
+    dir: &Path,
+) -> Result<(), ParseError> {
+    if let Some(size) = args.generate {
+        let Some(input) = solution.generate(args.seed, size) else {
+            eprintln!("this day can't make up inputs");
+            std::process::exit(1);
+        };
+        if let Err(e) = write_generated(&input, &args.input, dir) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.stream {
+        let Some(path) = args.input.path_in(dir) else {
+            eprintln!("only files can be streamed, since each part reads the input again");
+            std::process::exit(1);
+        };
+        let Some(answers) = solution.stream(&path) else {
+            eprintln!("this day can't stream its input");
+            std::process::exit(1);
+        };
+        let answers = answers?;
+        answers.print();
+        if let Err(e) = check_ledger(
+            &answers.strings(),
+            &args.input,
+            dir,
+            args.record,
+            args.verify,
+        ) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.check {
+        let report = check_source(&solution, &args.input, dir)?;
+        print!("{}", report);
+        if !report.is_ok() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(failure) = args.reduce {
+        return reduce_source(solution, failure, &args.input, dir);
+    }
+    if args.cross_check {
+        let report = cross_check_source(&solution, &args.input, dir)?;
+        print!("{}", report);
+        if !report.is_ok() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(options) = &args.visualize {
+        return visualize(solution, &args.input, dir, options);
+    }
+    if !args.bench && args.scale.is_none() {
+        let answers = try_solve_source(solution, &args.input, dir, args.parallel)?;
+        answers.print();
+        let answers = answers.strings();
+        if let Err(e) = check_ledger(&answers, &args.input, dir, args.record, args.verify) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let cwd = std::env::current_dir().unwrap_or_default().join(dir);
+    let name = cwd.file_name().unwrap_or_default().to_string_lossy();
+    if let Some(sizes) = &args.scale {
+        let config = bench::Config::from_env();
+        let Some(scalings) = bench::bench_scaling(&name, &solution, sizes, args.seed, &config)
+        else {
+            eprintln!("this day can't make up inputs");
+            std::process::exit(1);
+        };
+        bench::report_scaling(&scalings?, args.json.as_deref());
+        return Ok(());
+    }
+    let results = try_bench_source(&name, solution, &args.input, dir)?;
+    bench::report(&results, args.json.as_deref());
+    Ok(())
+}
+
+pub fn run<S: Solution + Send + 'static>(solution: S) {
+    exit_on_error(try_run(solution));
+}
+
+/// `register!(parse, part1, part2)` for a solution whose parser is applied to each line, with
+/// `try` before the parser if it returns a `Result`, and without `part2` if there isn't one. A
+/// trailing `; viz = frames` gives `--visualize` something to show, `; check = INVARIANTS` gives
+/// `--check` something to check, `; generate = generate` makes up inputs for `--generate`, and
+/// `; alt = ALTERNATIVES` adds other implementations to cross-check against.
 #[macro_export]
 macro_rules! register {
-    ($parser:expr, $part1:expr, $part2:expr) => {
-        fn main() {
-            $crate::run($parser, $part1, $part2);
+    (@impl $kind:ident $ctor:ident, $parser:expr, $part1:expr, $part2:expr $(, $then:ident)? $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        pub fn solution() -> impl $crate::Solution {
+            $crate::$kind::$ctor($parser, $part1, $part2)$(.$then())?$(.with_frames($frames))?$(.with_invariants($invariants))?$(.with_generator($generator))?$(.with_alternatives($alternatives))?
         }
 
-        #[cfg(test)]
-        extern crate test;
-
         #[cfg(test)]
         #[test]
         fn test() {
-            $crate::test($parser, $part1, $part2);
+            $crate::test(solution());
         }
+    };
 
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_parse(b: &mut test::Bencher) {
-            $crate::bench_parse($parser, b)
-        }
+    (try $parser:expr, $part1:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Lines try_new, $parser, $part1, $crate::no_part2, without_part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
 
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_part1(b: &mut test::Bencher) {
-            $crate::bench_solution($parser, $part1, b)
-        }
+    (try $parser:expr, $part1:expr, $part2:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Lines try_new, $parser, $part1, $part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
 
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_part2(b: &mut test::Bencher) {
-            $crate::bench_solution($parser, $part2, b)
-        }
+    ($parser:expr, $part1:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Lines new, $parser, $part1, $crate::no_part2, without_part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
+
+    ($parser:expr, $part1:expr, $part2:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Lines new, $parser, $part1, $part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
     };
 }
 
+/// Like `register!`, but the parser is handed the whole input.
 #[macro_export]
 macro_rules! register_alt {
-    ($parser:expr, $part1:expr, $part2:expr) => {
-        fn main() {
-            $crate::run_alt($parser, $part1, $part2);
-        }
+    (try $parser:expr, $part1:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Whole try_new, $parser, $part1, $crate::no_part2, without_part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
 
-        #[cfg(test)]
-        extern crate test;
+    (try $parser:expr, $part1:expr, $part2:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Whole try_new, $parser, $part1, $part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
+
+    ($parser:expr, $part1:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Whole new, $parser, $part1, $crate::no_part2, without_part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
+
+    ($parser:expr, $part1:expr, $part2:expr $(; viz = $frames:expr)? $(; check = $invariants:expr)? $(; generate = $generator:expr)? $(; alt = $alternatives:expr)?) => {
+        $crate::register!(@impl Whole new, $parser, $part1, $part2 $(; viz = $frames)? $(; check = $invariants)? $(; generate = $generator)? $(; alt = $alternatives)?);
+    };
+}
+
+/// Like `register!`, but each part takes an iterator of the parsed lines, which `--stream` feeds
+/// straight from the input file.
+#[macro_export]
+macro_rules! register_stream {
+    (@impl $ctor:ident, $parser:expr, $part1:expr, $part2:expr $(; generate = $generator:expr)?) => {
+        pub fn solution() -> impl $crate::Solution {
+            $crate::stream::Streamed::$ctor(
+                $parser,
+                |lines: &mut dyn Iterator<Item = _>| $part1(lines),
+                |lines: &mut dyn Iterator<Item = _>| $part2(lines),
+            )
+            $(.with_generator($generator))?
+        }
 
         #[cfg(test)]
         #[test]
         fn test() {
-            $crate::test_alt($parser, $part1, $part2);
-        }
-
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_parse(b: &mut test::Bencher) {
-            $crate::bench_parse_alt($parser, b)
-        }
-
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_part1(b: &mut test::Bencher) {
-            $crate::bench_solution_alt($parser, $part1, b)
-        }
-
-        #[cfg(all(test, not(debug_assertions)))]
-        #[bench]
-        fn bench_part2(b: &mut test::Bencher) {
-            $crate::bench_solution_alt($parser, $part2, b)
-        }
-    };
-}
-
-pub fn quad_neighbors(
-    x: usize,
-    y: usize,
-    w: usize,
-    h: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    let (x, y) = (x as isize, y as isize);
-    [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
-        .into_iter()
-        .filter(|&(x, y)| x >= 0 && y >= 0)
-        .map(|(x, y)| (x as usize, y as usize))
-        .filter(move |&(x, y)| x < w && y < h)
-}
-
-pub fn oct_neighbors(
-    x: usize,
-    y: usize,
-    w: usize,
-    h: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    let (x, y) = (x as isize, y as isize);
-    [
-        (x - 1, y),
-        (x + 1, y),
-        (x, y - 1),
-        (x, y + 1),
-        (x - 1, y - 1),
-        (x - 1, y + 1),
-        (x + 1, y - 1),
-        (x + 1, y + 1),
-    ]
-    .into_iter()
-    .filter(|&(x, y)| x >= 0 && y >= 0)
-    .map(|(x, y)| (x as usize, y as usize))
-    .filter(move |&(x, y)| x < w && y < h)
+            $crate::test(solution());
+        }
+    };
+
+    (try $parser:expr, $part1:expr, $part2:expr $(; generate = $generator:expr)?) => {
+        $crate::register_stream!(@impl try_new, $parser, $part1, $part2 $(; generate = $generator)?);
+    };
+
+    ($parser:expr, $part1:expr, $part2:expr $(; generate = $generator:expr)?) => {
+        $crate::register_stream!(@impl new, $parser, $part1, $part2 $(; generate = $generator)?);
+    };
 }
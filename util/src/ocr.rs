@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use crate::Grid;
+
+struct Font {
+    height: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+#[rustfmt::skip]
+const LARGE: Font = Font {
+    height: 10,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    Empty,
+    /// Only 6 and 10 pixel tall text is known.
+    Height(usize),
+    /// The `index`th glyph from the left (counting from 0), starting `x` pixels in, isn't a letter.
+    Glyph {
+        index: usize,
+        x: usize,
+        art: String,
+    },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no lit pixels to read"),
+            Self::Height(h) => write!(f, "text is {} pixels tall, not 6 or 10", h),
+            Self::Glyph { index, x, art } => write!(
+                f,
+                "couldn't recognise glyph {} (at x = {}):\n{}",
+                index, x, art
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+struct Image {
+    lit: HashSet<(usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+impl Image {
+    fn is_lit(&self, x: isize, y: usize) -> bool {
+        x >= 0 && self.lit.contains(&(x as usize, y))
+    }
+
+    fn column_is_blank(&self, x: usize) -> bool {
+        (0..self.height).all(|y| !self.lit.contains(&(x, y)))
+    }
+
+    fn matches(&self, x: isize, glyph: &[&str]) -> bool {
+        glyph.iter().enumerate().all(|(y, row)| {
+            row.bytes()
+                .enumerate()
+                .all(|(i, b)| self.is_lit(x + i as isize, y) == (b == b'#'))
+        })
+    }
+
+    fn art(&self, xs: std::ops::Range<usize>) -> String {
+        let rows = (0..self.height).map(|y| {
+            xs.clone()
+                .map(|x| if self.lit.contains(&(x, y)) { '#' } else { '.' })
+                .collect::<String>()
+        });
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    fn read(&self) -> Result<String, OcrError> {
+        let font = [SMALL, LARGE]
+            .into_iter()
+            .find(|font| font.height == self.height)
+            .ok_or(OcrError::Height(self.height))?;
+
+        let mut text = String::new();
+        let mut x = 0;
+        while x < self.width {
+            if self.column_is_blank(x) {
+                x += 1;
+                continue;
+            }
+
+            // Some glyphs, like the small I, start with a blank column.
+            let found = font.glyphs.iter().find_map(|&(c, glyph)| {
+                let indent = glyph
+                    .iter()
+                    .map(|row| row.find('#').unwrap_or(row.len()))
+                    .min()?;
+                let start = x as isize - indent as isize;
+                self.matches(start, glyph)
+                    .then_some((c, start + glyph[0].len() as isize))
+            });
+
+            match found {
+                Some((c, end)) => {
+                    text.push(c);
+                    x = end as usize;
+                }
+                None => {
+                    let end = (x..self.width)
+                        .find(|&x| self.column_is_blank(x))
+                        .unwrap_or(self.width);
+                    return Err(OcrError::Glyph {
+                        index: text.chars().count(),
+                        x,
+                        art: self.art(x..end),
+                    });
+                }
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// Reads the letters drawn by `points`, wherever they are.
+pub fn read_points<T: Copy + Into<i64>>(
+    points: impl IntoIterator<Item = (T, T)>,
+) -> Result<String, OcrError> {
+    let points = points
+        .into_iter()
+        .map(|(x, y)| (x.into(), y.into()))
+        .collect::<Vec<(i64, i64)>>();
+    let x_min = points.iter().map(|p| p.0).min().ok_or(OcrError::Empty)?;
+    let y_min = points.iter().map(|p| p.1).min().ok_or(OcrError::Empty)?;
+
+    let lit = points
+        .iter()
+        .map(|&(x, y)| ((x - x_min) as usize, (y - y_min) as usize))
+        .collect::<HashSet<_>>();
+    let image = Image {
+        width: lit.iter().map(|p| p.0 + 1).max().unwrap(),
+        height: lit.iter().map(|p| p.1 + 1).max().unwrap(),
+        lit,
+    };
+    image.read()
+}
+
+pub fn read_grid(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let lit = grid.iter().filter(|&(_, &lit)| lit).map(|(p, _)| p);
+    read_points(lit.map(|(x, y)| (x as i64, y as i64)))
+}
+
+/// Reads text drawn with `#`s, like the puzzle descriptions show it.
+pub fn read_art(art: &str) -> Result<String, OcrError> {
+    read_grid(&Grid::parse(art, |c| c == '#'))
+}
+
+#[cfg(test)]
+#[test]
+fn ocr() {
+    let small = "
+        .##..###..#..#.
+        #..#.#..#.#..#.
+        #..#.###..####.
+        ####.#..#.#..#.
+        #..#.#..#.#..#.
+        #..#.###..#..#.
+    ";
+    assert_eq!(read_art(small), Ok("ABH".into()));
+
+    let leading_i = [
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (2, 1),
+        (2, 2),
+        (2, 3),
+        (2, 4),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+    ];
+    assert_eq!(read_points::<i32>(leading_i), Ok("I".into()));
+
+    // Y is a column wider than the rest
+    let y = "
+        #...#.####
+        #...#....#
+        .#.#....#.
+        ..#....#..
+        ..#...#...
+        ..#...####
+    ";
+    assert_eq!(read_art(y), Ok("YZ".into()));
+
+    let large = LARGE.glyphs[13].1.join("\n");
+    assert_eq!(read_art(&large), Ok("X".into()));
+
+    // The top of the H
+    let smudged = small.replacen("..#..#.", "..#.##.", 1);
+    assert_eq!(
+        read_art(&smudged),
+        Err(OcrError::Glyph {
+            index: 2,
+            x: 10,
+            art: "#.##\n#..#\n####\n#..#\n#..#\n#..#".into()
+        })
+    );
+}
@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Runs `f` over `items` on up to `jobs` threads. The results are handed to `each` on the calling
+/// thread in the original order, each one as soon as everything before it is done.
+pub fn for_each_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+    mut each: impl FnMut(&T, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let (tx, next, f) = (tx.clone(), &next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                if tx.send((i, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut done = BTreeMap::new();
+        let mut printed = 0;
+        for (i, result) in rx {
+            done.insert(i, result);
+            while let Some(result) = done.remove(&printed) {
+                each(&items[printed], result);
+                printed += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+#[test]
+fn ordered() {
+    let items = (0..20u64).collect::<Vec<_>>();
+    let mut seen = vec![];
+    for_each_ordered(
+        &items,
+        4,
+        |&i| {
+            thread::sleep(std::time::Duration::from_millis(20 - i));
+            i * 2
+        },
+        |&i, doubled| seen.push((i, doubled)),
+    );
+    assert_eq!(seen, items.iter().map(|&i| (i, i * 2)).collect::<Vec<_>>());
+}
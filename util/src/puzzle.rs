@@ -0,0 +1,97 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The event that the top-level `dayN` crates belong to. Other years live in `YEAR/dayDD`.
+pub const DEFAULT_YEAR: u16 = 2021;
+
+/// Which puzzle a solution is for, written like `2021/day05`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Puzzle {
+    pub const fn new(year: u16, day: u8) -> Self {
+        Self { year, day }
+    }
+
+    /// The crate for this puzzle under the repository `root`, where its inputs, answers and
+    /// examples are kept too.
+    pub fn dir_in(&self, root: &Path) -> PathBuf {
+        if self.year == DEFAULT_YEAR {
+            root.join(format!("day{}", self.day))
+        } else {
+            root.join(self.year.to_string())
+                .join(format!("day{:02}", self.day))
+        }
+    }
+
+    /// The inverse of `dir_in`, e.g. for working out which puzzle the current directory is for.
+    pub fn from_dir(dir: &Path) -> Option<Self> {
+        let day = dir
+            .file_name()?
+            .to_str()?
+            .strip_prefix("day")?
+            .parse()
+            .ok()?;
+        let parent = dir
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|p| p.to_str());
+        let year = parent.and_then(parse_year).unwrap_or(DEFAULT_YEAR);
+        Some(Self { year, day })
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/day{:02}", self.year, self.day)
+    }
+}
+
+/// Events started in 2015, so any other number is a day rather than a year.
+pub fn parse_year(s: &str) -> Option<u16> {
+    s.parse().ok().filter(|&year| year >= 2015)
+}
+
+/// `5` or `day05`.
+pub fn parse_day(s: &str) -> Option<u8> {
+    let day = s.strip_prefix("day").unwrap_or(s).parse().ok()?;
+    (1..=25).contains(&day).then_some(day)
+}
+
+/// `2021/day05`, `2021/5`, or just the day for `DEFAULT_YEAR`.
+impl FromStr for Puzzle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (year, day) = match s.split_once('/') {
+            Some((year, day)) => (parse_year(year), day),
+            None => (Some(DEFAULT_YEAR), s),
+        };
+        match (year, parse_day(day)) {
+            (Some(year), Some(day)) => Ok(Self { year, day }),
+            _ => Err(format!("`{}` is not a puzzle, like `2021/day05`", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn puzzles() {
+    let p = Puzzle::new(2021, 5);
+    assert_eq!(p.to_string(), "2021/day05");
+    assert_eq!("2021/day05".parse(), Ok(p));
+    assert_eq!("day5".parse(), Ok(p));
+    assert!("2021/day26".parse::<Puzzle>().is_err());
+
+    let root = Path::new("aoc");
+    assert_eq!(p.dir_in(root), Path::new("aoc/day5"));
+    assert_eq!(Puzzle::from_dir(&p.dir_in(root)), Some(p));
+
+    let other = Puzzle::new(2022, 7);
+    assert_eq!(other.dir_in(root), Path::new("aoc/2022/day07"));
+    assert_eq!(Puzzle::from_dir(&other.dir_in(root)), Some(other));
+}
@@ -0,0 +1,271 @@
+//! Shrinks an input that makes a solution fail into a small reproducer, by delta debugging: blocks,
+//! then lines, then tokens are removed for as long as the failure still happens.
+
+use std::fmt::{self, Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::alt::CrossCheck;
+use crate::isolate::{self, Outcome};
+use crate::Solution;
+
+/// What has to keep happening for a smaller input to be kept. Inputs that don't parse never count,
+/// so that the reproducer is still a valid input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Failure {
+    /// Either part panics.
+    Panic,
+    /// An alternative implementation disagrees with the main one.
+    Mismatch,
+    /// Parsing and solving take longer than this.
+    Timeout(Duration),
+}
+
+impl Failure {
+    /// `panic`, `mismatch`, or `timeout=SECS`, with `timeout` alone meaning 5 seconds.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let secs = match s {
+            "panic" => return Ok(Self::Panic),
+            "mismatch" => return Ok(Self::Mismatch),
+            "timeout" => 5.0,
+            _ => s
+                .strip_prefix("timeout=")
+                .and_then(|secs| secs.parse::<f64>().ok())
+                .filter(|secs| *secs > 0.0)
+                .ok_or_else(|| format!("expected panic, mismatch or timeout=SECS, not `{}`", s))?,
+        };
+        Ok(Self::Timeout(Duration::from_secs_f64(secs)))
+    }
+
+    /// Parts that time out are left running in the background, like `isolate::solve` leaves them.
+    pub fn happens<S: Solution + Send + 'static>(self, solution: &Arc<S>, input: &str) -> bool {
+        let timeout = match self {
+            Self::Mismatch => {
+                let report = catch_unwind(AssertUnwindSafe(|| {
+                    let parsed = solution.parse(input).ok()?;
+                    Some(CrossCheck::run(&**solution, &parsed))
+                }));
+                return matches!(report, Ok(Some(report)) if !report.is_ok());
+            }
+            Self::Panic => None,
+            Self::Timeout(limit) => Some(limit),
+        };
+        let Outcome::Done(Ok(answers)) = isolate::solve(solution, input, false, timeout) else {
+            return false;
+        };
+        let parts = [Some(answers.part1), answers.part2];
+        parts.into_iter().flatten().any(|part| match part {
+            Outcome::Panicked(_) => self == Self::Panic,
+            Outcome::TimedOut(_) => timeout.is_some(),
+            Outcome::Done(_) => false,
+        })
+    }
+
+    /// Whether `input` still fails once the parts given up on while reducing have stopped, or the
+    /// timeout has passed again, whichever comes first. They share the machine with every check
+    /// after the first timeout, and could be all that made an input take too long. Parts that
+    /// never stop are still running then, and are counted in what's returned along with it.
+    pub fn confirmed<S: Solution + Send + 'static>(
+        self,
+        solution: &Arc<S>,
+        input: &str,
+    ) -> (bool, usize) {
+        if let Self::Timeout(limit) = self {
+            let since = Instant::now();
+            while isolate::running() > 0 && since.elapsed() < limit {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        let running = isolate::running();
+        (self.happens(solution, input), running)
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic => write!(f, "make a part panic"),
+            Self::Mismatch => write!(f, "make the implementations disagree"),
+            Self::Timeout(limit) => write!(f, "take over {:?}", limit),
+        }
+    }
+}
+
+/// What a pass of `reduce` removes pieces of.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Granularity {
+    Blocks,
+    Lines,
+    Tokens,
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == ','
+}
+
+/// The pieces of `body` that can be removed, each with the line it's on. A token takes the
+/// separators after it along with it.
+fn split(body: &str, granularity: Granularity) -> Vec<(usize, &str)> {
+    match granularity {
+        Granularity::Blocks => body.split("\n\n").enumerate().collect(),
+        Granularity::Lines => body.split('\n').enumerate().collect(),
+        Granularity::Tokens => {
+            let mut tokens = vec![];
+            for (i, line) in body.split('\n').enumerate() {
+                let mut start = 0;
+                let mut after_separator = false;
+                for (j, c) in line.char_indices() {
+                    if after_separator && !is_separator(c) {
+                        tokens.push((i, &line[start..j]));
+                        start = j;
+                    }
+                    after_separator = is_separator(c);
+                }
+                if start < line.len() {
+                    tokens.push((i, &line[start..]));
+                }
+            }
+            tokens
+        }
+    }
+}
+
+fn join(pieces: &[(usize, &str)], granularity: Granularity, lines: usize) -> String {
+    let texts = || pieces.iter().map(|&(_, text)| text).collect::<Vec<_>>();
+    match granularity {
+        Granularity::Blocks => texts().join("\n\n"),
+        Granularity::Lines => texts().join("\n"),
+        Granularity::Tokens => {
+            // Lines are never removed this way, even once they're empty
+            let mut out = vec![String::new(); lines];
+            for &(i, token) in pieces {
+                out[i].push_str(token);
+            }
+            let out = out.iter().map(|line| line.trim_end_matches(is_separator));
+            out.collect::<Vec<_>>().join("\n")
+        }
+    }
+}
+
+/// The indices of a subset of `0..len` that still fails, which can't lose any one chunk of the
+/// last granularity tried and still fail. Subsets are only tested if they're smaller than `len`.
+fn ddmin(len: usize, mut fails: impl FnMut(&[usize]) -> bool) -> Vec<usize> {
+    let mut kept = (0..len).collect::<Vec<_>>();
+    let mut n = 2;
+    while kept.len() >= 2 {
+        let chunks = kept
+            .chunks(kept.len().div_ceil(n))
+            .map(<[_]>::to_vec)
+            .collect::<Vec<_>>();
+        if let Some(chunk) = chunks.iter().find(|chunk| fails(chunk)) {
+            kept = chunk.clone();
+            n = 2;
+            continue;
+        }
+        // With two chunks, each one's complement is the other
+        let complements = (0..chunks.len()).filter(|_| chunks.len() > 2).map(|i| {
+            let before = chunks[..i].iter().flatten();
+            before
+                .chain(chunks[i + 1..].iter().flatten())
+                .copied()
+                .collect::<Vec<_>>()
+        });
+        if let Some(complement) = complements.into_iter().find(|c| fails(c)) {
+            kept = complement;
+            n = (n - 1).max(2);
+            continue;
+        }
+        if n >= kept.len() {
+            break;
+        }
+        n = (n * 2).min(kept.len());
+    }
+    if kept.len() == 1 && fails(&[]) {
+        kept.clear();
+    }
+    kept
+}
+
+fn pass(input: &str, granularity: Granularity, fails: &mut impl FnMut(&str) -> bool) -> String {
+    let newline = if input.ends_with('\n') { "\n" } else { "" };
+    let body = input.strip_suffix('\n').unwrap_or(input);
+    let lines = body.split('\n').count();
+    let pieces = split(body, granularity);
+
+    let mut fails = |kept: &[usize]| {
+        let kept = kept.iter().map(|&i| pieces[i]).collect::<Vec<_>>();
+        fails(&(join(&kept, granularity, lines) + newline))
+    };
+    let kept = ddmin(pieces.len(), &mut fails);
+    if kept.len() == pieces.len() {
+        return input.to_owned();
+    }
+    let kept = kept.iter().map(|&i| pieces[i]).collect::<Vec<_>>();
+    join(&kept, granularity, lines) + newline
+}
+
+/// The smallest input found that `fails`, or `None` if `input` itself doesn't. `progress` is told
+/// how small the input is after each pass.
+pub fn reduce(
+    input: &str,
+    mut fails: impl FnMut(&str) -> bool,
+    mut progress: impl FnMut(Granularity, usize),
+) -> Option<String> {
+    if !fails(input) {
+        return None;
+    }
+    let mut current = input.to_owned();
+    loop {
+        let before = current.len();
+        for granularity in [Granularity::Blocks, Granularity::Lines, Granularity::Tokens] {
+            current = pass(&current, granularity, &mut fails);
+            progress(granularity, current.len());
+        }
+        // Removing tokens can let more lines go, and so on
+        if current.len() == before {
+            return Some(current);
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn reduces() {
+    let input = "1,2,3\n4,5,6\n\n7 8 9\n10\n";
+    let fails = |s: &str| s.contains('5') && s.contains('9');
+    let mut passes = 0;
+    let reduced = reduce(input, fails, |_, _| passes += 1);
+    assert_eq!(reduced.as_deref(), Some("5\n9\n"));
+    assert_eq!(passes % 3, 0);
+    assert_eq!(reduce(input, |s| s.contains("11"), |_, _| ()), None);
+
+    let pieces = split("on x=1..2,y=3\nb", Granularity::Tokens);
+    assert_eq!(pieces, [(0, "on "), (0, "x=1..2,"), (0, "y=3"), (1, "b")]);
+    let kept = [pieces[0], pieces[1], pieces[3]];
+    assert_eq!(join(&kept, Granularity::Tokens, 2), "on x=1..2\nb");
+
+    assert_eq!(
+        Failure::parse("timeout=0.5"),
+        Ok(Failure::Timeout(Duration::from_millis(500)))
+    );
+    assert!(Failure::parse("timeout=0").is_err());
+
+    // Nines spin forever, so the reproducer is confirmed alongside the part left spinning
+    let solution = Arc::new(crate::Lines::new(
+        crate::parse_unwrap::<u32>,
+        |input: &[u32]| loop {
+            if !input.contains(&9) {
+                break 0;
+            }
+            std::thread::yield_now();
+        },
+        crate::no_part2,
+    ));
+    let failure = Failure::Timeout(Duration::from_millis(20));
+    let reduced = reduce("1\n9\n2\n", |s| failure.happens(&solution, s), |_, _| ());
+    assert_eq!(reduced.as_deref(), Some("9\n"));
+    let (confirmed, running) = failure.confirmed(&solution, "9\n");
+    assert!(confirmed && running > 0);
+    assert!(!failure.confirmed(&solution, "2\n").0);
+}
@@ -0,0 +1,300 @@
+//! Template-driven parsing: `scan::<(i32, i32)>("x=3..7", "x={}..{}")`.
+//!
+//! `{}` captures a single value, and `{SEP}` (e.g. `{,}`, or `{ }` for whitespace) captures a
+//! list of them for a `Vec`. A space in a template matches any run of whitespace.
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'t> {
+    Literal(&'t str),
+    Capture(Option<&'t str>),
+}
+
+fn tokenize(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}').expect("unclosed `{` in template");
+        if start > 0 {
+            tokens.push(Token::Literal(&rest[..start]));
+        }
+        let sep = &rest[start + 1..end];
+        tokens.push(Token::Capture((!sep.is_empty()).then_some(sep)));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Literal(rest));
+    }
+
+    for pair in tokens.windows(2) {
+        if let [Token::Capture(_), Token::Capture(_)] = pair {
+            panic!("captures in `{}` need something between them", template);
+        }
+    }
+    tokens
+}
+
+/// What's left of `s` after `literal`, if it starts with it.
+fn strip_literal<'a>(s: &'a str, literal: &str) -> Option<&'a str> {
+    let mut rest = s;
+    for (i, part) in literal.split(' ').enumerate() {
+        if i > 0 {
+            let trimmed = rest.trim_start();
+            if trimmed.len() == rest.len() && !rest.is_empty() {
+                return None;
+            }
+            rest = trimmed;
+        }
+        rest = rest.strip_prefix(part)?;
+    }
+    Some(rest)
+}
+
+/// A captured piece of text, and the separator it was captured with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capture<'a> {
+    pub text: &'a str,
+    pub sep: Option<&'a str>,
+}
+
+/// Matches `s` (trimmed) against `template`, returning the raw captures.
+pub fn captures<'a>(s: &'a str, template: &'a str) -> Result<Vec<Capture<'a>>, ParseError> {
+    let tokens = tokenize(template);
+    let s = s.trim();
+    let mut rest = s;
+    let mut captures = vec![];
+
+    for (i, &token) in tokens.iter().enumerate() {
+        match token {
+            Token::Literal(literal) => {
+                rest = strip_literal(rest, literal).ok_or_else(|| {
+                    // As many characters as the literal has, which needn't be as many bytes
+                    let end = rest.char_indices().nth(literal.chars().count());
+                    let found = &rest[..end.map_or(rest.len(), |(j, _)| j)];
+                    ParseError::at(s, found, format!("expected `{}`", literal))
+                })?;
+            }
+            Token::Capture(sep) => {
+                let end = match tokens.get(i + 1) {
+                    Some(Token::Literal(next)) => rest
+                        .char_indices()
+                        .skip(1)
+                        .map(|(j, _)| j)
+                        .find(|&j| strip_literal(&rest[j..], next).is_some())
+                        .ok_or_else(|| {
+                            ParseError::at(s, rest, format!("expected a value, then `{}`", next))
+                        })?,
+                    _ => rest.len(),
+                };
+                if end == 0 {
+                    return Err(ParseError::at(s, rest, "expected a value"));
+                }
+                captures.push(Capture {
+                    text: &rest[..end],
+                    sep,
+                });
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        return Err(ParseError::at(s, rest, "unexpected trailing text"));
+    }
+    Ok(captures)
+}
+
+pub trait FromCapture: Sized {
+    fn from_capture(capture: Capture<'_>) -> Result<Self, ParseError>;
+}
+
+/// Lets types with a `FromStr` impl be captured, e.g. `util::scannable!(Var, Rval);`.
+#[macro_export]
+macro_rules! scannable {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $crate::scan::FromCapture for $ty {
+                fn from_capture(capture: $crate::scan::Capture<'_>) -> Result<Self, $crate::ParseError> {
+                    let text = capture.text.trim();
+                    text.parse::<$ty>()
+                        .map_err(|e| $crate::ParseError::from(e).within(capture.text, text))
+                }
+            }
+        )*
+    };
+}
+
+scannable!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String
+);
+
+impl<T: FromCapture> FromCapture for Vec<T> {
+    fn from_capture(capture: Capture<'_>) -> Result<Self, ParseError> {
+        let items: Box<dyn Iterator<Item = &str>> = match capture.sep.map(str::trim) {
+            None | Some("") => Box::new(capture.text.split_whitespace()),
+            Some(sep) => Box::new(capture.text.split(sep)),
+        };
+        items
+            .map(|item| {
+                let item_capture = Capture {
+                    text: item,
+                    sep: None,
+                };
+                T::from_capture(item_capture).map_err(|e| e.within(capture.text, item))
+            })
+            .collect()
+    }
+}
+
+/// One capture, or a tuple of them in template order. Errors point into `s`.
+pub trait Captures: Sized {
+    fn from_captures(s: &str, captures: &[Capture<'_>]) -> Result<Self, ParseError>;
+}
+
+impl<T: FromCapture> Captures for T {
+    fn from_captures(s: &str, captures: &[Capture<'_>]) -> Result<Self, ParseError> {
+        let [capture] = captures else {
+            panic!("expected 1 capture, template has {}", captures.len());
+        };
+        T::from_capture(*capture).map_err(|e| e.within(s, capture.text))
+    }
+}
+
+macro_rules! tuple_captures {
+    ($n:literal: $($t:ident)*) => {
+        impl<$($t: FromCapture),*> Captures for ($($t,)*) {
+            fn from_captures(s: &str, captures: &[Capture<'_>]) -> Result<Self, ParseError> {
+                let [$($t),*] = captures else {
+                    panic!("expected {} captures, template has {}", $n, captures.len());
+                };
+                Ok(($($t::from_capture(*$t).map_err(|e| e.within(s, $t.text))?,)*))
+            }
+        }
+    };
+}
+
+#[allow(non_snake_case)]
+mod tuples {
+    use super::*;
+
+    tuple_captures!(2: A B);
+    tuple_captures!(3: A B C);
+    tuple_captures!(4: A B C D);
+    tuple_captures!(5: A B C D E);
+    tuple_captures!(6: A B C D E F);
+    tuple_captures!(7: A B C D E F G);
+    tuple_captures!(8: A B C D E F G H);
+}
+
+/// Matches `s` against `template`, parsing the captures into `T`: one type, or a tuple of them.
+pub fn scan<T: Captures>(s: &str, template: &str) -> Result<T, ParseError> {
+    let s = s.trim();
+    T::from_captures(s, &captures(s, template)?)
+}
+
+/// Scans every non-blank line of `text`, with line numbers in the errors.
+pub fn scan_lines<T: Captures>(text: &str, template: &str) -> Result<Vec<T>, ParseError> {
+    Section { text, line: 1 }.scan_lines(template)
+}
+
+/// A blank-line separated block of the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    /// Where the section starts in the whole input, counting from 1.
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        let first = self.line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(i, line)| (first + i, line))
+            .filter(|(_, line)| !line.trim().is_empty())
+    }
+
+    pub fn scan<T: Captures>(&self, template: &str) -> Result<T, ParseError> {
+        scan(self.text, template).map_err(|e| {
+            // The line the error is on, which needn't be the section's first
+            let e = e.within(self.text, self.text.trim());
+            let before = e.text.get(..e.span.start).unwrap_or_default();
+            let line = self.line + before.matches('\n').count();
+            e.with_line(line)
+        })
+    }
+
+    pub fn scan_lines<T: Captures>(&self, template: &str) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .map(|(n, line)| scan(line, template).map_err(|e| e.with_line(n)))
+            .collect()
+    }
+}
+
+/// Splits `s` at blank lines.
+pub fn sections(s: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut offset = 0;
+    for (i, line) in s.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((line, begin)) = start.take() {
+                sections.push(Section {
+                    text: s[begin..offset].trim_end(),
+                    line,
+                });
+            }
+        } else if start.is_none() {
+            start = Some((i + 1, offset));
+        }
+        offset += line.len();
+    }
+    if let Some((line, begin)) = start {
+        sections.push(Section {
+            text: s[begin..].trim_end(),
+            line,
+        });
+    }
+    sections
+}
+
+#[cfg(test)]
+#[test]
+fn templates() {
+    let area = scan::<(i32, i32, i32, i32)>(
+        "target area: x=20..30, y=-10..-5",
+        "target area: x={}..{}, y={}..{}",
+    );
+    assert_eq!(area, Ok((20, 30, -10, -5)));
+
+    let rule = scan::<(String, char)>("CH -> B", "{} -> {}");
+    assert_eq!(rule, Ok(("CH".to_owned(), 'B')));
+
+    let row = scan::<Vec<u8>>(" 8  2 23  4 24", "{ }");
+    assert_eq!(row, Ok(vec![8, 2, 23, 4, 24]));
+
+    let e = scan::<(i32, i32)>("x=3..y", "x={}..{}").unwrap_err();
+    assert_eq!(e.span, 5..6);
+
+    let e = scan::<(i32, i32)>("x=3,4", "x={}..{}").unwrap_err();
+    assert_eq!(e.message, "expected a value, then `..`");
+
+    let e = scan::<(i32, i32)>("aé=3..4", "x={}..{}").unwrap_err();
+    assert_eq!((e.span, e.message.as_str()), (0..3, "expected `x=`"));
+
+    let input = "7,4,9\n\n22 13\n 8  2\n\n\n1 22\n";
+    let sections = sections(input);
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].scan::<Vec<u8>>("{,}"), Ok(vec![7, 4, 9]));
+    assert_eq!(sections[1].line, 3);
+    let e = sections[2].scan_lines::<(u8, char)>("{} {}").unwrap_err();
+    assert_eq!(e.line, Some(7));
+    let section = Section {
+        text: "22 13\n 8  x",
+        line: 3,
+    };
+    let e = section.scan::<Vec<u8>>("{ }").unwrap_err();
+    assert_eq!((e.line, &e.text[e.span]), (Some(4), "x"));
+}
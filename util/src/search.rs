@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The result of a search: how far away the goal is, and enough bookkeeping to say how to get there.
+#[derive(Debug, Clone)]
+pub struct Found<N, C> {
+    pub goal: N,
+    pub cost: C,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C> Found<N, C> {
+    /// The nodes from the start to the goal, inclusive.
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.goal.clone()];
+        while let Some(prev) = self.parents.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+/// `neighbors` yields `(node, step cost)` pairs. `heuristic` must never overestimate.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if cost > best[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(Found {
+                goal: node,
+                cost,
+                parents,
+            });
+        }
+
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            match best.entry(next.clone()) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => *e.get_mut() = next_cost,
+                Entry::Vacant(e) => drop(e.insert(next_cost)),
+            }
+            parents.insert(next.clone(), node.clone());
+            heap.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    None
+}
+
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, but every step costs 1.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, depth)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Found {
+                goal: node,
+                cost: depth,
+                parents,
+            });
+        }
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Counts the distinct paths from `start` to any goal, which aren't explored past. The graph must
+/// be acyclic.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N: Clone + Eq + Hash, I: IntoIterator<Item = N>>(
+        node: N,
+        neighbors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, usize>,
+    ) -> usize {
+        if is_goal(&node) {
+            return 1;
+        }
+        if let Some(&n) = memo.get(&node) {
+            return n;
+        }
+        let n = neighbors(&node)
+            .into_iter()
+            .map(|next| count(next, neighbors, is_goal, memo))
+            .sum();
+        memo.insert(node, n);
+        n
+    }
+
+    count(start, &mut neighbors, &mut is_goal, &mut HashMap::new())
+}
+
+#[cfg(test)]
+#[test]
+fn search() {
+    let grid = crate::Grid::parse("131\n191\n111", |c| c.to_digit(10).unwrap());
+    let neighbors = |&p: &_| grid.quad_neighbors(p).map(|n| (n, grid[n]));
+    let goal = (2, 2);
+
+    let found = dijkstra((0, 0), neighbors, |&p| p == goal).unwrap();
+    assert_eq!(found.cost, 4);
+    assert_eq!(found.path(), [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+
+    let manhattan = |&(x, y): &(usize, usize)| (2 - x + 2 - y) as u32;
+    assert_eq!(
+        astar((0, 0), neighbors, manhattan, |&p| p == goal)
+            .unwrap()
+            .cost,
+        4
+    );
+
+    let found = bfs((0, 0), |&p| grid.quad_neighbors(p), |&p| p == goal).unwrap();
+    assert_eq!((found.cost, found.path().len()), (4, 5));
+
+    let monotone = |&(x, y): &(usize, usize)| {
+        [(x + 1, y), (x, y + 1)]
+            .into_iter()
+            .filter(|&p| grid.contains(p))
+    };
+    assert_eq!(count_paths((0, 0), monotone, |&p| p == goal), 6);
+}
@@ -0,0 +1,261 @@
+//! Inputs too big to hold in memory, for `--stream`. Each part is fed the input's lines one at a
+//! time, parsed as they're read from a memory-mapped file, or from any `BufRead`.
+//!
+//! ```ignore
+//! fn part1(depths: impl Iterator<Item = u16>) -> usize { ... }
+//! util::register_stream!(try util::parse::<u16>, part1, part2);
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use memmap2::Mmap;
+
+use crate::{alloc, generate, phase, Answers, ParseError, Parser, Solution, Solved, Timings};
+
+/// Parses one line, counting from 1, like `try_parse_lines` does.
+fn parse_line<T>(i: usize, bytes: &[u8], parser: &Parser<T>) -> Result<T, ParseError> {
+    let line = std::str::from_utf8(bytes).map_err(|_| {
+        ParseError::whole(&String::from_utf8_lossy(bytes), "not valid UTF-8").with_line(i + 1)
+    })?;
+    let trimmed = line.trim();
+    parser(trimmed).map_err(|e| e.within(line, trimmed).with_line(i + 1))
+}
+
+/// Lines parsed as they're read, reusing one buffer. They end early if reading fails, and the
+/// error is kept for `take_error`.
+pub struct Lines<'a, R, T> {
+    reader: R,
+    parser: &'a Parser<T>,
+    buf: Vec<u8>,
+    line: usize,
+    error: Option<io::Error>,
+}
+
+pub fn lines<R: BufRead, T>(reader: R, parser: &Parser<T>) -> Lines<'_, R, T> {
+    Lines {
+        reader,
+        parser,
+        buf: vec![],
+        line: 0,
+        error: None,
+    }
+}
+
+impl<R, T> Lines<'_, R, T> {
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+impl<R: BufRead, T> Iterator for Lines<'_, R, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+        match self.reader.read_until(b'\n', &mut self.buf) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(e) => {
+                self.error = Some(e);
+                return None;
+            }
+        }
+        let bytes = self.buf.strip_suffix(b"\n").unwrap_or(&self.buf);
+        self.line += 1;
+        Some(parse_line(self.line - 1, bytes, self.parser))
+    }
+}
+
+/// The lines of a mapped file, split the same way as `str::lines`.
+pub fn mapped_lines<'a, T>(
+    bytes: &'a [u8],
+    parser: &'a Parser<T>,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    let bytes = (!bytes.is_empty()).then(|| bytes.strip_suffix(b"\n").unwrap_or(bytes));
+    bytes
+        .into_iter()
+        .flat_map(|bytes| bytes.split(|&b| b == b'\n'))
+        .enumerate()
+        .map(|(i, line)| parse_line(i, line, parser))
+}
+
+/// Hands `f` the parsed values until the first error, which is returned instead of its result.
+/// Anything after the point where `f` stops reading isn't checked.
+pub fn consume<T, R>(
+    lines: impl Iterator<Item = Result<T, ParseError>>,
+    f: impl FnOnce(&mut dyn Iterator<Item = T>) -> R,
+) -> Result<R, ParseError> {
+    let mut error = None;
+    let mut values = lines.map_while(|line| line.map_err(|e| error = Some(e)).ok());
+    let result = f(&mut values);
+    drop(values);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// A file that can be read from the start once per part.
+pub enum Source {
+    Mapped(Mmap),
+    /// For files that can't be mapped, like empty ones on some platforms.
+    Buffered(PathBuf),
+}
+
+impl Source {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: inputs aren't expected to change while they're being solved. If one does, a
+        // line might be torn, but it's only ever read as bytes.
+        match unsafe { Mmap::map(&file) } {
+            Ok(map) => Ok(Self::Mapped(map)),
+            Err(_) => Ok(Self::Buffered(path.to_owned())),
+        }
+    }
+
+    pub fn pass<T, R>(
+        &self,
+        parser: &Parser<T>,
+        f: impl FnOnce(&mut dyn Iterator<Item = T>) -> R,
+    ) -> io::Result<Result<R, ParseError>> {
+        match self {
+            Self::Mapped(map) => Ok(consume(mapped_lines(map, parser), f)),
+            Self::Buffered(path) => {
+                let mut lines = lines(BufReader::new(File::open(path)?), parser);
+                let result = consume(&mut lines, f);
+                lines.take_error().map_or(Ok(result), Err)
+            }
+        }
+    }
+}
+
+type Part<In, Out> = fn(&mut dyn Iterator<Item = In>) -> Out;
+
+/// A solution whose parts each make one pass over the parsed lines, so that they needn't all be in
+/// memory at once. It still runs like `Lines` when there's no `--stream`.
+pub struct Streamed<In, Out1, Out2> {
+    parser: Parser<In>,
+    part1: Part<In, Out1>,
+    part2: Part<In, Out2>,
+    generator: Option<generate::Generator>,
+}
+
+impl<In, Out1, Out2> Streamed<In, Out1, Out2> {
+    pub fn try_new<P, E>(parser: P, part1: Part<In, Out1>, part2: Part<In, Out2>) -> Self
+    where
+        P: Fn(&str) -> Result<In, E> + Send + Sync + 'static,
+        E: Into<ParseError>,
+    {
+        Self {
+            parser: Box::new(move |s| parser(s).map_err(Into::into)),
+            part1,
+            part2,
+            generator: None,
+        }
+    }
+
+    pub fn new<P>(parser: P, part1: Part<In, Out1>, part2: Part<In, Out2>) -> Self
+    where
+        P: Fn(&str) -> In + Send + Sync + 'static,
+    {
+        Self::try_new(move |s| Ok::<_, ParseError>(parser(s)), part1, part2)
+    }
+
+    pub fn with_generator(self, generator: generate::Generator) -> Self {
+        Self {
+            generator: Some(generator),
+            ..self
+        }
+    }
+
+    fn pass<Out>(
+        &self,
+        source: &Source,
+        path: &Path,
+        part: Part<In, Out>,
+    ) -> ((Result<Out, ParseError>, Duration), alloc::Allocs) {
+        phase(|| {
+            source
+                .pass(&self.parser, part)
+                .unwrap_or_else(|e| Err(ParseError::unreadable(path, e)))
+        })
+    }
+}
+
+impl<In, Out1, Out2> Solution for Streamed<In, Out1, Out2>
+where
+    In: Clone + Sync,
+    Out1: std::fmt::Display + Send,
+    Out2: std::fmt::Display + Send,
+{
+    type Input = Vec<In>;
+    type Output1 = Out1;
+    type Output2 = Out2;
+
+    fn parse(&self, input: &str) -> Result<Vec<In>, ParseError> {
+        crate::try_parse_lines(input, &self.parser)
+    }
+
+    fn part1(&self, input: &Vec<In>) -> Out1 {
+        (self.part1)(&mut input.iter().cloned())
+    }
+
+    fn part2(&self, input: &Vec<In>) -> Out2 {
+        (self.part2)(&mut input.iter().cloned())
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        let generator = self.generator?;
+        Some(generator(&mut generate::Rng::new(seed), size))
+    }
+
+    /// Parsing happens during each part, so it's timed as part of them.
+    fn stream(&self, path: &Path) -> Option<Solved<Out1, Out2>> {
+        let source = match Source::open(path) {
+            Ok(source) => source,
+            Err(e) => return Some(Err(ParseError::unreadable(path, e))),
+        };
+        let ((part1, part1_time), part1_allocs) = self.pass(&source, path, self.part1);
+        let ((part2, part2_time), part2_allocs) = self.pass(&source, path, self.part2);
+        let answers = part1.and_then(|part1| {
+            Ok(Answers {
+                part1,
+                part2: Some(part2?),
+                timings: Timings {
+                    parse: Duration::ZERO,
+                    part1: part1_time,
+                    part2: part2_time,
+                },
+                memory: alloc::enabled().then_some(alloc::Usage {
+                    parse: alloc::Allocs::default(),
+                    part1: part1_allocs,
+                    part2: part2_allocs,
+                }),
+            })
+        });
+        Some(answers.map_err(|e| e.with_file(path)))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn streaming() {
+    let parser: Parser<u8> = Box::new(crate::parse);
+    let text = "1\n2\r\n3\n";
+    let sum = |values: &mut dyn Iterator<Item = u8>| values.sum::<u8>();
+    assert_eq!(consume(lines(text.as_bytes(), &parser), sum), Ok(6));
+    assert_eq!(consume(mapped_lines(text.as_bytes(), &parser), sum), Ok(6));
+    assert_eq!(mapped_lines(b"", &parser).count(), 0);
+
+    let e = consume(mapped_lines(b"1\nx\n3", &parser), sum).unwrap_err();
+    assert_eq!(e.line, Some(2));
+    let e = consume(lines(&b"1\n\xff\n"[..], &parser), sum).unwrap_err();
+    assert_eq!((e.line, e.message.as_str()), (Some(2), "not valid UTF-8"));
+
+    let mut lines = lines(BufReader::new(File::open(".").unwrap()), &parser);
+    assert!(lines.next().is_none());
+    assert!(lines.take_error().is_some());
+}
@@ -0,0 +1,79 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::bench::format_nanos;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+
+    pub fn phases(&self) -> [(&'static str, Duration); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+pub fn format_duration(d: Duration) -> String {
+    format_nanos(d.as_nanos() as f64)
+}
+
+/// A table of per-day timings, followed by the slowest phases across all of them.
+pub fn summary(days: &[(String, Timings)]) -> String {
+    let mut out = format!(
+        "{:<10} {:>12} {:>12} {:>12} {:>12}\n",
+        "puzzle", "parse", "part1", "part2", "total"
+    );
+    for (name, t) in days {
+        writeln!(
+            out,
+            "{:<10} {:>12} {:>12} {:>12} {:>12}",
+            name,
+            format_duration(t.parse),
+            format_duration(t.part1),
+            format_duration(t.part2),
+            format_duration(t.total())
+        )
+        .unwrap();
+    }
+
+    let grand_total = days.iter().map(|(_, t)| t.total()).sum::<Duration>();
+    writeln!(out, "{:<10} {:>51}", "total", format_duration(grand_total)).unwrap();
+
+    let mut phases = days
+        .iter()
+        .flat_map(|(name, t)| t.phases().map(|(phase, d)| (name, phase, d)))
+        .collect::<Vec<_>>();
+    phases.sort_by_key(|&(_, _, d)| std::cmp::Reverse(d));
+
+    writeln!(out, "\nslowest:").unwrap();
+    for (name, phase, d) in phases.into_iter().take(5) {
+        let share = d.as_secs_f64() / grand_total.as_secs_f64().max(f64::MIN_POSITIVE);
+        writeln!(
+            out,
+            "  {:<10} {:<6} {:>12} {:>5.1}%",
+            name,
+            phase,
+            format_duration(d),
+            share * 100.0
+        )
+        .unwrap();
+    }
+    out
+}
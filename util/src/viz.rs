@@ -0,0 +1,220 @@
+//! Frames of a simulation, for `--visualize`. They can be played in the terminal, or written to a
+//! directory as numbered PPM or PNG images, or as one animated GIF.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::Grid;
+
+pub type Rgb = [u8; 3];
+pub type Frame = Grid<Rgb>;
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+/// A simulation state that can be drawn.
+pub trait Visualize {
+    fn frame(&self) -> Frame;
+}
+
+/// A cell of a grid that can be drawn as one pixel.
+pub trait Pixel {
+    fn color(&self) -> Rgb;
+}
+
+impl Pixel for Rgb {
+    fn color(&self) -> Rgb {
+        *self
+    }
+}
+
+impl Pixel for bool {
+    fn color(&self) -> Rgb {
+        if *self {
+            [255, 255, 255]
+        } else {
+            [24, 24, 32]
+        }
+    }
+}
+
+impl<T: Pixel> Visualize for Grid<T> {
+    fn frame(&self) -> Frame {
+        self.map(Pixel::color)
+    }
+}
+
+pub const BLACK: Rgb = [0, 0, 0];
+
+/// From `from` to `to`, as `t` goes from 0 to 1.
+pub fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| (from[i] as f64 + (to[i] as f64 - from[i] as f64) * t).round() as u8)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Terminal,
+    Ppm(PathBuf),
+    Png(PathBuf),
+    Gif(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub target: Target,
+    pub fps: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            target: Target::Terminal,
+            fps: 10,
+        }
+    }
+}
+
+/// Shows or writes every frame, returning how many there were.
+pub fn play(frames: impl Iterator<Item = Frame>, options: &Options) -> io::Result<usize> {
+    let delay = Duration::from_secs_f64(1.0 / options.fps.max(1) as f64);
+    match &options.target {
+        Target::Terminal => terminal(frames, delay),
+        Target::Ppm(dir) => numbered(dir, "ppm", frames, |frame, file| {
+            file.write_all(&ppm(frame))
+        }),
+        Target::Png(dir) => numbered(dir, "png", frames, write_png),
+        Target::Gif(dir) => write_gif(dir, frames, delay),
+    }
+}
+
+/// Two pixels to a character, using the foreground and background colours of `▀`.
+fn terminal(frames: impl Iterator<Item = Frame>, delay: Duration) -> io::Result<usize> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    write!(out, "\x1b[2J\x1b[?25l")?;
+    let mut count = 0;
+    for frame in frames {
+        write!(out, "\x1b[H")?;
+        for y in (0..frame.height()).step_by(2) {
+            for x in 0..frame.width() {
+                let [r, g, b] = frame[(x, y)];
+                let [r2, g2, b2] = frame.get((x, y + 1)).copied().unwrap_or(BLACK);
+                write!(
+                    out,
+                    "\x1b[38;2;{};{};{};48;2;{};{};{}m▀",
+                    r, g, b, r2, g2, b2
+                )?;
+            }
+            writeln!(out, "\x1b[0m\x1b[K")?;
+        }
+        count += 1;
+        writeln!(out, "frame {}\x1b[J", count)?;
+        out.flush()?;
+        std::thread::sleep(delay);
+    }
+    write!(out, "\x1b[?25h")?;
+    out.flush()?;
+    Ok(count)
+}
+
+/// Every frame at the size of the largest, centred, and scaled up so that they're easy to see.
+fn canvas(frames: impl Iterator<Item = Frame>) -> Vec<Frame> {
+    let frames = frames.collect::<Vec<_>>();
+    let width = frames.iter().map(Grid::width).max().unwrap_or(0);
+    let height = frames.iter().map(Grid::height).max().unwrap_or(0);
+    let scale = (256 / width.max(height).max(1)).max(1);
+
+    frames
+        .iter()
+        .map(|frame| {
+            let (dx, dy) = ((width - frame.width()) / 2, (height - frame.height()) / 2);
+            Grid::from_fn(width * scale, height * scale, |(x, y)| {
+                let (x, y) = (x / scale, y / scale);
+                let inner = (x.wrapping_sub(dx), y.wrapping_sub(dy));
+                frame.get(inner).copied().unwrap_or(BLACK)
+            })
+        })
+        .collect()
+}
+
+fn bytes(frame: &Frame) -> Vec<u8> {
+    frame.values().flatten().copied().collect()
+}
+
+fn ppm(frame: &Frame) -> Vec<u8> {
+    let mut out = format!("P6\n{} {}\n255\n", frame.width(), frame.height()).into_bytes();
+    out.extend(bytes(frame));
+    out
+}
+
+fn write_png(frame: &Frame, file: &mut BufWriter<File>) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(file, frame.width() as u32, frame.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&bytes(frame))
+        .map_err(io::Error::other)
+}
+
+fn numbered(
+    dir: &Path,
+    extension: &str,
+    frames: impl Iterator<Item = Frame>,
+    mut write: impl FnMut(&Frame, &mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let frames = canvas(frames);
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame{:04}.{}", i, extension));
+        let mut file = BufWriter::new(File::create(path)?);
+        write(frame, &mut file)?;
+        file.flush()?;
+    }
+    Ok(frames.len())
+}
+
+fn write_gif(
+    dir: &Path,
+    frames: impl Iterator<Item = Frame>,
+    delay: Duration,
+) -> io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let frames = canvas(frames);
+    let Some(first) = frames.first() else {
+        return Ok(0);
+    };
+    let size = |n: usize| u16::try_from(n).map_err(|_| io::Error::other("frame too big for a GIF"));
+    let (width, height) = (size(first.width())?, size(first.height())?);
+
+    let file = BufWriter::new(File::create(dir.join("animation.gif"))?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for frame in &frames {
+        let mut gif_frame = gif::Frame::from_rgb_speed(width, height, &bytes(frame), 10);
+        gif_frame.delay = (delay.as_millis() / 10) as u16;
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+    Ok(frames.len())
+}
+
+#[cfg(test)]
+#[test]
+fn frames() {
+    let small = Grid::parse("#.\n.#", |c| c == '#');
+    assert_eq!(small.frame()[(1, 1)], [255, 255, 255]);
+    assert_eq!(
+        ppm(&small.frame().map(|_| [1, 2, 3]))[..13],
+        *b"P6\n2 2\n255\n\x01\x02"
+    );
+
+    let big = Grid::new(4, 2, [9, 9, 9]);
+    let padded = canvas([small.frame(), big].into_iter());
+    // Scaled up 64 times, with the small frame centred
+    assert_eq!((padded[0].width(), padded[0].height()), (256, 128));
+    assert_eq!(padded[0][(64, 0)], [255, 255, 255]);
+    assert_eq!(padded[0][(0, 0)], BLACK);
+    assert_eq!(blend(BLACK, [200, 100, 0], 0.5), [100, 50, 0]);
+}